@@ -0,0 +1,259 @@
+use crate::graph::Cost;
+use std::collections::VecDeque;
+use std::ops::Sub;
+
+const NIL: usize = usize::MAX;
+
+///最小全域有向木(Chu-Liu/Edmonds法、O(m log n))
+///
+///n:頂点数
+///
+///root:根
+///
+///edges:辺リスト(from, to, cost)(添字を辺番号とする)
+///
+///(重みの総和, 各頂点に入る辺の辺番号(根はNone))を返す。根から到達できない頂点があればNoneを返す。
+pub fn min_arborescence<T>(
+    n: usize,
+    root: usize,
+    edges: &[(usize, usize, T)],
+) -> Option<(T, Vec<Option<usize>>)>
+where
+    T: Cost + Sub<Output = T>,
+{
+    let mut heap = SkewHeap::new(edges);
+    let mut top = vec![NIL; n];
+    for (id, &(_, to, _)) in edges.iter().enumerate() {
+        top[to] = heap.merge(top[to], id);
+    }
+
+    let mut uf = RollbackUnionFind::new(n);
+    let mut cost = T::zero();
+    let mut seen = vec![NIL; n];
+    seen[root] = root;
+    let mut path = vec![0; n];
+    let mut queue = vec![0; n];
+    let mut incoming = vec![NIL; n];
+    //(縮約後の頂点, 縮約前のUnionFindの時刻, 閉路の辺)
+    let mut cycles = VecDeque::new();
+
+    for s in 0..n {
+        let mut u = s;
+        let mut qi = 0;
+        while seen[u] == NIL {
+            if top[u] == NIL {
+                return None;
+            }
+            let (w, id) = heap.top(top[u]);
+            heap.add(top[u], w);
+            top[u] = heap.pop(top[u]);
+            queue[qi] = id;
+            path[qi] = u;
+            qi += 1;
+            seen[u] = s;
+            cost = cost.plus(w);
+            u = uf.find(edges[id].0);
+            if seen[u] == s {
+                //閉路を見つけたので縮約する。
+                let mut cycle = NIL;
+                let end = qi;
+                let time = uf.time();
+                loop {
+                    qi -= 1;
+                    let w = path[qi];
+                    cycle = heap.merge(cycle, top[w]);
+                    if !uf.unite(u, w) {
+                        break;
+                    }
+                }
+                u = uf.find(u);
+                top[u] = cycle;
+                seen[u] = NIL;
+                cycles.push_front((u, time, queue[qi..end].to_vec()));
+            }
+        }
+        for &id in &queue[..qi] {
+            incoming[uf.find(edges[id].1)] = id;
+        }
+    }
+
+    //縮約を新しいものから順に戻して、各頂点に入る辺を復元する。
+    for (u, time, cycle) in cycles {
+        uf.rollback(time);
+        let in_edge = incoming[u];
+        for id in cycle {
+            incoming[uf.find(edges[id].1)] = id;
+        }
+        incoming[uf.find(edges[in_edge].1)] = in_edge;
+    }
+
+    let parent = (0..n)
+        .map(|v| if v == root { None } else { Some(incoming[v]) })
+        .collect();
+    Some((cost, parent))
+}
+
+//遅延評価つきのSkew Heap(ノードは辺番号)
+//
+//lazyは部分木全体から引く量(符号なし整数でも負の値を作らない)
+struct SkewHeap<T> {
+    key: Vec<T>,
+    lazy: Vec<Option<T>>,
+    left: Vec<usize>,
+    right: Vec<usize>,
+}
+
+impl<T> SkewHeap<T>
+where
+    T: Cost + Sub<Output = T>,
+{
+    fn new<A, B>(edges: &[(A, B, T)]) -> SkewHeap<T> {
+        SkewHeap {
+            key: edges.iter().map(|e| e.2).collect(),
+            lazy: vec![None; edges.len()],
+            left: vec![NIL; edges.len()],
+            right: vec![NIL; edges.len()],
+        }
+    }
+
+    fn push_down(&mut self, a: usize) {
+        if let Some(d) = self.lazy[a].take() {
+            self.key[a] = self.key[a] - d;
+            for c in [self.left[a], self.right[a]] {
+                if c != NIL {
+                    self.lazy[c] = Some(match self.lazy[c] {
+                        Some(x) => x.plus(d),
+                        None => d,
+                    });
+                }
+            }
+        }
+    }
+
+    //ヒープ全体の値からwを引く。
+    fn add(&mut self, a: usize, w: T) {
+        self.lazy[a] = Some(match self.lazy[a] {
+            Some(x) => x.plus(w),
+            None => w,
+        });
+    }
+
+    fn top(&mut self, a: usize) -> (T, usize) {
+        self.push_down(a);
+        (self.key[a], a)
+    }
+
+    fn pop(&mut self, a: usize) -> usize {
+        self.push_down(a);
+        self.merge(self.left[a], self.right[a])
+    }
+
+    //再帰が深くならないように右の経路をスタックで辿る。
+    fn merge(&mut self, mut a: usize, mut b: usize) -> usize {
+        let mut stack = vec![];
+        while a != NIL && b != NIL {
+            self.push_down(a);
+            self.push_down(b);
+            if self.key[a] > self.key[b] {
+                std::mem::swap(&mut a, &mut b);
+            }
+            stack.push(a);
+            let next = self.right[a];
+            a = b;
+            b = next;
+        }
+        let mut r = if a == NIL { b } else { a };
+        while let Some(x) = stack.pop() {
+            self.right[x] = self.left[x];
+            self.left[x] = r;
+            r = x;
+        }
+        r
+    }
+}
+
+//経路圧縮なしのUnionFind(操作を巻き戻せる)
+struct RollbackUnionFind {
+    data: Vec<isize>,
+    history: Vec<(usize, isize)>,
+}
+
+impl RollbackUnionFind {
+    fn new(n: usize) -> RollbackUnionFind {
+        RollbackUnionFind {
+            data: vec![-1; n],
+            history: vec![],
+        }
+    }
+
+    fn find(&self, mut x: usize) -> usize {
+        while self.data[x] >= 0 {
+            x = self.data[x] as usize;
+        }
+        x
+    }
+
+    fn time(&self) -> usize {
+        self.history.len()
+    }
+
+    fn rollback(&mut self, time: usize) {
+        while self.history.len() > time {
+            let (i, v) = self.history.pop().unwrap();
+            self.data[i] = v;
+        }
+    }
+
+    fn unite(&mut self, a: usize, b: usize) -> bool {
+        let mut a = self.find(a);
+        let mut b = self.find(b);
+        if a == b {
+            return false;
+        }
+        if self.data[a] > self.data[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.history.push((a, self.data[a]));
+        self.history.push((b, self.data[b]));
+        self.data[a] += self.data[b];
+        self.data[b] = a as isize;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arborescence_works() {
+        let edges = vec![
+            (0, 1, 10_i64),
+            (0, 2, 10),
+            (0, 3, 10),
+            (3, 1, 1),
+            (1, 2, 1),
+            (2, 3, 1),
+        ];
+        let (cost, parent) = min_arborescence(4, 0, &edges).unwrap();
+        assert_eq!(cost, 12);
+        assert_eq!(parent[0], None);
+        let total = parent.iter().flatten().map(|&id| edges[id].2).sum::<i64>();
+        assert_eq!(total, cost);
+        for v in 1..4 {
+            assert_eq!(edges[parent[v].unwrap()].1, v);
+        }
+
+        let edges = vec![(0, 1, 3_i64), (1, 2, 2), (2, 1, 1)];
+        let (cost, parent) = min_arborescence(3, 0, &edges).unwrap();
+        assert_eq!(cost, 5);
+        assert_eq!(parent, vec![None, Some(0), Some(1)]);
+
+        let edges = vec![(0, 1, 3_u64), (1, 2, 2), (2, 1, 1)];
+        let (cost, parent) = min_arborescence(3, 0, &edges).unwrap();
+        assert_eq!(cost, 5);
+        assert_eq!(parent, vec![None, Some(0), Some(1)]);
+
+        assert!(min_arborescence(3, 0, &[(0, 1, 1_i64), (2, 1, 1)]).is_none());
+    }
+}
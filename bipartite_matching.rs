@@ -0,0 +1,187 @@
+use std::collections::VecDeque;
+
+///二部グラフの最大マッチング(Hopcroft-Karp法、O(m√n))
+pub struct BipartiteMatching {
+    graph: Vec<Vec<usize>>,
+    match_left: Vec<Option<usize>>,
+    match_right: Vec<Option<usize>>,
+    preprocessing_complete: bool,
+}
+
+impl BipartiteMatching {
+    ///left:左側の頂点数, right:右側の頂点数
+    pub fn new(left: usize, right: usize) -> BipartiteMatching {
+        BipartiteMatching {
+            graph: vec![vec![]; left],
+            match_left: vec![None; left],
+            match_right: vec![None; right],
+            preprocessing_complete: false,
+        }
+    }
+
+    ///左側の頂点lと右側の頂点rを結ぶ辺を追加する。
+    pub fn add_edge(&mut self, l: usize, r: usize) {
+        assert!(!self.preprocessing_complete);
+        assert!(r < self.match_right.len());
+        self.graph[l].push(r);
+    }
+
+    ///最大マッチングを計算し、マッチした(左側の頂点, 右側の頂点)の組を返す。
+    pub fn max_matching(&mut self) -> Vec<(usize, usize)> {
+        if !self.preprocessing_complete {
+            self.preprocessing_complete = true;
+            while self.augment() {}
+        }
+        self.match_left
+            .iter()
+            .enumerate()
+            .filter_map(|(l, r)| r.map(|r| (l, r)))
+            .collect()
+    }
+
+    ///左側の頂点lとマッチしている右側の頂点
+    pub fn match_left(&self, l: usize) -> Option<usize> {
+        assert!(self.preprocessing_complete);
+        self.match_left[l]
+    }
+
+    ///右側の頂点rとマッチしている左側の頂点
+    pub fn match_right(&self, r: usize) -> Option<usize> {
+        assert!(self.preprocessing_complete);
+        self.match_right[r]
+    }
+
+    ///最小頂点被覆(左側の頂点のリスト, 右側の頂点のリスト)を返す。
+    pub fn min_vertex_cover(&self) -> (Vec<usize>, Vec<usize>) {
+        let (left, right) = self.alternating_reachable();
+        (
+            (0..left.len()).filter(|&l| !left[l]).collect(),
+            (0..right.len()).filter(|&r| right[r]).collect(),
+        )
+    }
+
+    ///最大独立集合(左側の頂点のリスト, 右側の頂点のリスト)を返す。
+    pub fn max_independent_set(&self) -> (Vec<usize>, Vec<usize>) {
+        let (left, right) = self.alternating_reachable();
+        (
+            (0..left.len()).filter(|&l| left[l]).collect(),
+            (0..right.len()).filter(|&r| !right[r]).collect(),
+        )
+    }
+
+    //左側の未マッチの頂点から交互路で到達できる頂点(Kőnigの定理)
+    fn alternating_reachable(&self) -> (Vec<bool>, Vec<bool>) {
+        assert!(self.preprocessing_complete);
+        let mut left = vec![false; self.graph.len()];
+        let mut right = vec![false; self.match_right.len()];
+        let mut stack = (0..self.graph.len())
+            .filter(|&l| self.match_left[l].is_none())
+            .collect::<Vec<_>>();
+        for &l in &stack {
+            left[l] = true;
+        }
+        while let Some(l) = stack.pop() {
+            for &r in &self.graph[l] {
+                if right[r] {
+                    continue;
+                }
+                right[r] = true;
+                if let Some(next) = self.match_right[r] {
+                    if !left[next] {
+                        left[next] = true;
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+        (left, right)
+    }
+
+    //最短の増加路を極大にとって増やす。増やせなければfalseを返す。
+    fn augment(&mut self) -> bool {
+        let n = self.graph.len();
+        let mut dist = vec![usize::MAX; n];
+        let mut queue = VecDeque::new();
+        for (l, m) in self.match_left.iter().enumerate() {
+            if m.is_none() {
+                dist[l] = 0;
+                queue.push_back(l);
+            }
+        }
+        let mut found = false;
+        while let Some(l) = queue.pop_front() {
+            for &r in &self.graph[l] {
+                match self.match_right[r] {
+                    None => found = true,
+                    Some(next) => {
+                        if dist[next] == usize::MAX {
+                            dist[next] = dist[l] + 1;
+                            queue.push_back(next);
+                        }
+                    }
+                }
+            }
+        }
+        if !found {
+            return false;
+        }
+
+        let mut it = vec![0; n];
+        let mut stack = vec![];
+        for s in 0..n {
+            if self.match_left[s].is_some() {
+                continue;
+            }
+            stack.push(s);
+            while let Some(&l) = stack.last() {
+                if it[l] == self.graph[l].len() {
+                    dist[l] = usize::MAX;
+                    stack.pop();
+                    continue;
+                }
+                let r = self.graph[l][it[l]];
+                match self.match_right[r] {
+                    None => {
+                        for &x in &stack {
+                            let y = self.graph[x][it[x]];
+                            self.match_left[x] = Some(y);
+                            self.match_right[y] = Some(x);
+                        }
+                        stack.clear();
+                    }
+                    Some(next) if dist[next] == dist[l] + 1 => stack.push(next),
+                    _ => it[l] += 1,
+                }
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bipartite_matching_works() {
+        let mut bm = BipartiteMatching::new(4, 3);
+        for &(l, r) in &[(0, 0), (0, 1), (1, 0), (2, 1), (2, 2), (3, 2)] {
+            bm.add_edge(l, r);
+        }
+        let m = bm.max_matching();
+        assert_eq!(m.len(), 3);
+        for &(l, r) in &m {
+            assert_eq!(bm.match_left(l), Some(r));
+            assert_eq!(bm.match_right(r), Some(l));
+        }
+
+        let (cl, cr) = bm.min_vertex_cover();
+        assert_eq!(cl.len() + cr.len(), 3);
+        let (il, ir) = bm.max_independent_set();
+        assert_eq!(il.len() + ir.len(), 4);
+        for &(l, r) in &[(0, 0), (0, 1), (1, 0), (2, 1), (2, 2), (3, 2)] {
+            assert!(cl.contains(&l) || cr.contains(&r));
+            assert!(!(il.contains(&l) && ir.contains(&r)));
+        }
+    }
+}
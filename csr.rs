@@ -0,0 +1,167 @@
+///CSR(Compressed Sparse Row)形式のグラフ
+///
+///頂点vの隣接辺はadj(v)で(行き先, 重み)のスライスとして得られる。重みなしのグラフはW=()とする。
+///
+///辺番号は構築に使った辺リストの添字になる。(無向辺は両方向で同じ番号を持つ)
+#[derive(Clone, Debug)]
+pub struct Csr<W> {
+    start: Vec<usize>,
+    edges: Vec<(usize, W)>,
+    ids: Vec<usize>,
+}
+
+impl<W> Csr<W>
+where
+    W: Copy,
+{
+    ///辺リスト(from, to, weight)から有向グラフを構築する。
+    pub fn directed(n: usize, edges: &[(usize, usize, W)]) -> Csr<W> {
+        Csr::build(
+            n,
+            edges
+                .iter()
+                .enumerate()
+                .map(|(id, &(from, to, w))| (from, to, w, id)),
+        )
+    }
+
+    ///辺リスト(u, v, weight)から無向グラフを構築する。
+    pub fn undirected(n: usize, edges: &[(usize, usize, W)]) -> Csr<W> {
+        Csr::build(
+            n,
+            edges.iter().enumerate().flat_map(|(id, &(u, v, w))| {
+                let rev = if u == v { None } else { Some((v, u, w, id)) };
+                Some((u, v, w, id)).into_iter().chain(rev)
+            }),
+        )
+    }
+
+    ///隣接リスト(graph.rsの形式)から構築する。辺番号は隣接リストを順に読んだ順になる。
+    pub fn from_adjacency(graph: &[Vec<(usize, W)>]) -> Csr<W> {
+        let mut start = Vec::with_capacity(graph.len() + 1);
+        let mut edges = vec![];
+        start.push(0);
+        for v in graph {
+            edges.extend_from_slice(v);
+            start.push(edges.len());
+        }
+        let ids = (0..edges.len()).collect();
+        Csr { start, edges, ids }
+    }
+
+    fn build<I>(n: usize, edges: I) -> Csr<W>
+    where
+        I: Iterator<Item = (usize, usize, W, usize)> + Clone,
+    {
+        let mut start = vec![0; n + 1];
+        for (from, _, _, _) in edges.clone() {
+            start[from + 1] += 1;
+        }
+        for i in 0..n {
+            start[i + 1] += start[i];
+        }
+        let mut pos = start.clone();
+        let mut buf = vec![None; start[n]];
+        let mut ids = vec![0; start[n]];
+        for (from, to, w, id) in edges {
+            buf[pos[from]] = Some((to, w));
+            ids[pos[from]] = id;
+            pos[from] += 1;
+        }
+        let edges = buf.into_iter().map(Option::unwrap).collect();
+        Csr { start, edges, ids }
+    }
+}
+
+impl Csr<()> {
+    ///重みなしの辺リストから有向グラフを構築する。
+    pub fn directed_unweighted(n: usize, edges: &[(usize, usize)]) -> Csr<()> {
+        Csr::build(
+            n,
+            edges
+                .iter()
+                .enumerate()
+                .map(|(id, &(from, to))| (from, to, (), id)),
+        )
+    }
+
+    ///重みなしの辺リストから無向グラフを構築する。
+    pub fn undirected_unweighted(n: usize, edges: &[(usize, usize)]) -> Csr<()> {
+        Csr::build(
+            n,
+            edges.iter().enumerate().flat_map(|(id, &(u, v))| {
+                let rev = if u == v { None } else { Some((v, u, (), id)) };
+                Some((u, v, (), id)).into_iter().chain(rev)
+            }),
+        )
+    }
+
+    ///重みなしの隣接リスト(graph_search.rsの形式)から構築する。
+    pub fn from_unweighted(graph: &[Vec<usize>]) -> Csr<()> {
+        let mut start = Vec::with_capacity(graph.len() + 1);
+        let mut edges = vec![];
+        start.push(0);
+        for v in graph {
+            edges.extend(v.iter().map(|&to| (to, ())));
+            start.push(edges.len());
+        }
+        let ids = (0..edges.len()).collect();
+        Csr { start, edges, ids }
+    }
+}
+
+impl<W> Csr<W> {
+    ///頂点数
+    pub fn len(&self) -> usize {
+        self.start.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    ///隣接リストに格納されている辺の本数(無向辺は2本と数える)
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    ///頂点vから出る辺の(行き先, 重み)
+    pub fn adj(&self, v: usize) -> &[(usize, W)] {
+        &self.edges[self.start[v]..self.start[v + 1]]
+    }
+
+    ///頂点vから出る辺の辺番号(adj(v)と同じ順)
+    pub fn edge_ids(&self, v: usize) -> &[usize] {
+        &self.ids[self.start[v]..self.start[v + 1]]
+    }
+
+    ///頂点vから出る辺の行き先
+    pub fn neighbors(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adj(v).iter().map(|e| e.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csr_works() {
+        let g = Csr::directed(3, &[(0, 1, 5), (2, 0, 3), (0, 2, 1)]);
+        assert_eq!(g.len(), 3);
+        assert_eq!(g.adj(0), &[(1, 5), (2, 1)]);
+        assert_eq!(g.edge_ids(0), &[0, 2]);
+        assert_eq!(g.adj(1), &[]);
+        assert_eq!(g.adj(2), &[(0, 3)]);
+
+        let g = Csr::undirected_unweighted(3, &[(0, 1), (1, 2), (2, 2)]);
+        assert_eq!(g.neighbors(1).collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(g.edge_ids(1), &[0, 1]);
+        assert_eq!(g.neighbors(2).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(g.edge_count(), 5);
+
+        let g = Csr::from_unweighted(&[vec![1, 2], vec![], vec![0]]);
+        assert_eq!(g.neighbors(0).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(g.edge_ids(2), &[2]);
+    }
+}
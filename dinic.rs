@@ -0,0 +1,165 @@
+use std::collections::VecDeque;
+
+///最大流(Dinic法)
+#[derive(Clone)]
+pub struct Dinic {
+    graph: Vec<Vec<DinicEdge>>,
+    pos: Vec<(usize, usize)>,
+}
+
+#[derive(Clone)]
+struct DinicEdge {
+    to: usize,
+    rev: usize,
+    cap: i64,
+}
+
+impl Dinic {
+    pub fn new(n: usize) -> Dinic {
+        Dinic {
+            graph: (0..n).map(|_| vec![]).collect(),
+            pos: vec![],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.graph.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.graph.is_empty()
+    }
+
+    ///容量capの辺from->toを追加し、辺番号を返す。
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: i64) -> usize {
+        assert!(cap >= 0);
+        let id = self.pos.len();
+        let from_idx = self.graph[from].len();
+        let to_idx = self.graph[to].len() + if from == to { 1 } else { 0 };
+        self.pos.push((from, from_idx));
+        self.graph[from].push(DinicEdge {
+            to,
+            rev: to_idx,
+            cap,
+        });
+        self.graph[to].push(DinicEdge {
+            to: from,
+            rev: from_idx,
+            cap: 0,
+        });
+        id
+    }
+
+    ///辺の(from, to, 容量, 流量)を返す。
+    pub fn get_edge(&self, id: usize) -> (usize, usize, i64, i64) {
+        let (from, idx) = self.pos[id];
+        let e = &self.graph[from][idx];
+        let re = &self.graph[e.to][e.rev];
+        (from, e.to, e.cap + re.cap, re.cap)
+    }
+
+    ///すべての辺の(from, to, 容量, 流量)を辺番号の順に返す。
+    pub fn edges(&self) -> Vec<(usize, usize, i64, i64)> {
+        (0..self.pos.len()).map(|id| self.get_edge(id)).collect()
+    }
+
+    ///辺の容量と流量を変更する。
+    pub fn change_edge(&mut self, id: usize, cap: i64, flow: i64) {
+        assert!(0 <= flow && flow <= cap);
+        let (from, idx) = self.pos[id];
+        let (to, rev) = (self.graph[from][idx].to, self.graph[from][idx].rev);
+        self.graph[from][idx].cap = cap - flow;
+        self.graph[to][rev].cap = flow;
+    }
+
+    ///sからtへの最大流を流し、流量を返す。
+    pub fn flow(&mut self, s: usize, t: usize) -> i64 {
+        self.flow_with_limit(s, t, i64::MAX)
+    }
+
+    ///sからtへ流量limitを上限として流し、流量を返す。
+    pub fn flow_with_limit(&mut self, s: usize, t: usize, limit: i64) -> i64 {
+        let n = self.graph.len();
+        let mut total = 0;
+        while total < limit {
+            let mut level = vec![usize::MAX; n];
+            level[s] = 0;
+            let mut queue = VecDeque::new();
+            queue.push_back(s);
+            while let Some(v) = queue.pop_front() {
+                for e in &self.graph[v] {
+                    if e.cap > 0 && level[e.to] == usize::MAX {
+                        level[e.to] = level[v] + 1;
+                        queue.push_back(e.to);
+                    }
+                }
+            }
+            if level[t] == usize::MAX {
+                break;
+            }
+            let mut iter = vec![0; n];
+            loop {
+                let f = self.dfs(s, t, limit - total, &level, &mut iter);
+                if f == 0 {
+                    break;
+                }
+                total += f;
+            }
+        }
+        total
+    }
+
+    ///最大流を流した後に呼ぶと、最小カットのs側の頂点をtrueとした配列を返す。
+    pub fn min_cut(&self, s: usize) -> Vec<bool> {
+        let mut visited = vec![false; self.graph.len()];
+        let mut stack = vec![s];
+        visited[s] = true;
+        while let Some(v) = stack.pop() {
+            for e in &self.graph[v] {
+                if e.cap > 0 && !visited[e.to] {
+                    visited[e.to] = true;
+                    stack.push(e.to);
+                }
+            }
+        }
+        visited
+    }
+
+    fn dfs(&mut self, v: usize, t: usize, f: i64, level: &[usize], iter: &mut [usize]) -> i64 {
+        if v == t {
+            return f;
+        }
+        while iter[v] < self.graph[v].len() {
+            let DinicEdge { to, rev, cap } = self.graph[v][iter[v]];
+            if cap > 0 && level[v] < level[to] {
+                let d = self.dfs(to, t, f.min(cap), level, iter);
+                if d > 0 {
+                    self.graph[v][iter[v]].cap -= d;
+                    self.graph[to][rev].cap += d;
+                    return d;
+                }
+            }
+            iter[v] += 1;
+        }
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dinic_works() {
+        let mut g = Dinic::new(4);
+        let e0 = g.add_edge(0, 1, 2);
+        g.add_edge(0, 2, 1);
+        g.add_edge(1, 2, 1);
+        g.add_edge(1, 3, 1);
+        g.add_edge(2, 3, 2);
+        assert_eq!(g.flow(0, 3), 3);
+        assert_eq!(g.get_edge(e0), (0, 1, 2, 2));
+        assert_eq!(g.edges().len(), 5);
+        assert_eq!(g.min_cut(0), vec![true, false, false, false]);
+    }
+}
@@ -0,0 +1,203 @@
+///支配木(Lengauer-Tarjan法、O(m log n))
+///
+///rootからvへのすべてのパスが通る頂点を、vの支配頂点という。
+pub struct DominatorTree {
+    idom: Vec<Option<usize>>,
+    tin: Vec<usize>,
+    tout: Vec<usize>,
+}
+
+impl DominatorTree {
+    ///graph:有向グラフの隣接リスト, root:始点
+    pub fn new(graph: &[Vec<usize>], root: usize) -> DominatorTree {
+        let idom = dominator_tree(graph, root);
+        let n = graph.len();
+        let mut children = vec![vec![]; n];
+        for (v, &p) in idom.iter().enumerate() {
+            if let Some(p) = p {
+                if v != root {
+                    children[p].push(v);
+                }
+            }
+        }
+        //支配木上のオイラーツアーで祖先判定をする。
+        let mut tin = vec![usize::MAX; n];
+        let mut tout = vec![usize::MAX; n];
+        let mut timer = 0;
+        let mut stack = vec![(root, 0)];
+        tin[root] = 0;
+        while let Some((v, i)) = stack.pop() {
+            if i < children[v].len() {
+                stack.push((v, i + 1));
+                let c = children[v][i];
+                timer += 1;
+                tin[c] = timer;
+                stack.push((c, 0));
+            } else {
+                tout[v] = timer;
+            }
+        }
+        DominatorTree { idom, tin, tout }
+    }
+
+    ///vの直接支配頂点(rootはroot自身、到達不能な頂点はNone)
+    pub fn idom(&self, v: usize) -> Option<usize> {
+        self.idom[v]
+    }
+
+    pub fn idoms(&self) -> &[Option<usize>] {
+        &self.idom
+    }
+
+    pub fn is_reachable(&self, v: usize) -> bool {
+        self.idom[v].is_some()
+    }
+
+    ///uがvを支配するか(rootからvへのすべてのパスがuを通るか)
+    pub fn dominates(&self, u: usize, v: usize) -> bool {
+        self.is_reachable(u)
+            && self.is_reachable(v)
+            && self.tin[u] <= self.tin[v]
+            && self.tout[v] <= self.tout[u]
+    }
+
+    ///vを支配する頂点のリスト(v自身からrootまで)
+    pub fn dominators(&self, mut v: usize) -> Vec<usize> {
+        let mut r = vec![];
+        if !self.is_reachable(v) {
+            return r;
+        }
+        r.push(v);
+        while let Some(p) = self.idom[v].filter(|&p| p != v) {
+            r.push(p);
+            v = p;
+        }
+        r
+    }
+}
+
+///各頂点の直接支配頂点を求める(Lengauer-Tarjan法)
+///
+///graph:有向グラフの隣接リスト, root:始点
+///
+///rootはSome(root)、rootから到達できない頂点はNoneになる。
+pub fn dominator_tree(graph: &[Vec<usize>], root: usize) -> Vec<Option<usize>> {
+    const NONE: usize = usize::MAX;
+    let n = graph.len();
+    //DFSの訪問順に番号を振り直す。
+    let mut ord = vec![NONE; n];
+    let mut vs = vec![];
+    let mut par = vec![];
+    let mut stack = vec![(root, NONE)];
+    while let Some((v, p)) = stack.pop() {
+        if ord[v] != NONE {
+            continue;
+        }
+        ord[v] = vs.len();
+        vs.push(v);
+        par.push(p);
+        for &to in graph[v].iter().rev() {
+            if ord[to] == NONE {
+                stack.push((to, ord[v]));
+            }
+        }
+    }
+    let cnt = vs.len();
+    let mut rev = vec![vec![]; cnt];
+    for (v, adj) in graph.iter().enumerate() {
+        if ord[v] == NONE {
+            continue;
+        }
+        for &to in adj {
+            rev[ord[to]].push(ord[v]);
+        }
+    }
+
+    let mut semi = (0..cnt).collect::<Vec<_>>();
+    let mut idom = vec![0; cnt];
+    let mut anc = vec![NONE; cnt];
+    let mut label = (0..cnt).collect::<Vec<_>>();
+    let mut bucket = vec![vec![]; cnt];
+    let mut path = vec![];
+    for w in (1..cnt).rev() {
+        for &v in &rev[w] {
+            let u = eval(v, &mut anc, &mut label, &semi, &mut path);
+            semi[w] = semi[w].min(semi[u]);
+        }
+        bucket[semi[w]].push(w);
+        anc[w] = par[w];
+        for v in std::mem::take(&mut bucket[par[w]]) {
+            let u = eval(v, &mut anc, &mut label, &semi, &mut path);
+            idom[v] = if semi[u] < semi[v] { u } else { par[w] };
+        }
+    }
+    for w in 1..cnt {
+        if idom[w] != semi[w] {
+            idom[w] = idom[idom[w]];
+        }
+    }
+
+    let mut r = vec![None; n];
+    r[root] = Some(root);
+    for w in 1..cnt {
+        r[vs[w]] = Some(vs[idom[w]]);
+    }
+    r
+}
+
+//経路圧縮をしながら、祖先のうちsemiが最小の頂点を求める。
+fn eval(
+    v: usize,
+    anc: &mut [usize],
+    label: &mut [usize],
+    semi: &[usize],
+    path: &mut Vec<usize>,
+) -> usize {
+    const NONE: usize = usize::MAX;
+    if anc[v] == NONE {
+        return v;
+    }
+    let mut x = v;
+    while anc[anc[x]] != NONE {
+        path.push(x);
+        x = anc[x];
+    }
+    while let Some(x) = path.pop() {
+        let a = anc[x];
+        if semi[label[a]] < semi[label[x]] {
+            label[x] = label[a];
+        }
+        anc[x] = anc[a];
+    }
+    label[v]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dominator_tree_works() {
+        let graph = vec![
+            vec![1, 2],
+            vec![3],
+            vec![3, 4],
+            vec![5],
+            vec![5],
+            vec![1],
+            vec![0],
+        ];
+        let idom = dominator_tree(&graph, 0);
+        assert_eq!(
+            idom,
+            vec![Some(0), Some(0), Some(0), Some(0), Some(2), Some(0), None]
+        );
+
+        let tree = DominatorTree::new(&graph, 0);
+        assert!(tree.dominates(2, 4));
+        assert!(!tree.dominates(2, 5));
+        assert!(!tree.dominates(6, 0));
+        assert_eq!(tree.dominators(4), vec![4, 2, 0]);
+        assert_eq!(tree.dominators(6), vec![]);
+    }
+}
@@ -0,0 +1,258 @@
+use crate::dinic::Dinic;
+use crate::lca::Lca;
+use std::collections::HashSet;
+use std::fmt::{Display, Write};
+use std::path::Path;
+
+const COLORS: [&str; 10] = [
+    "lightblue",
+    "lightpink",
+    "palegreen",
+    "khaki",
+    "plum",
+    "lightsalmon",
+    "paleturquoise",
+    "wheat",
+    "lightgray",
+    "lightcoral",
+];
+
+///Graphviz(DOT形式)で出力するときの設定
+#[derive(Clone, Debug, Default)]
+pub struct DotOptions {
+    ///有向グラフとして出力するか(falseなら隣接リストの両方向の辺を1本にまとめる)
+    pub directed: bool,
+    ///頂点のラベル(Noneなら頂点番号)
+    pub labels: Option<Vec<String>>,
+    ///強調表示するパス(頂点列)
+    pub path: Vec<usize>,
+    ///各頂点の連結成分の番号(同じ成分を同じ色で塗る)
+    pub components: Option<Vec<usize>>,
+}
+
+///重みなしグラフ(graph.rsの形式)をDOT形式の文字列にする。
+pub fn dot_graph(graph: &[Vec<usize>], opt: &DotOptions) -> String {
+    let edges = internal_edges(
+        graph
+            .iter()
+            .enumerate()
+            .flat_map(|(v, adj)| adj.iter().map(move |&to| (v, to, None))),
+        opt.directed,
+    );
+    internal_dot(graph.len(), &edges, opt)
+}
+
+///重みつきグラフ(graph.rsの形式)をDOT形式の文字列にする。重みは辺のラベルになる。
+pub fn dot_weighted_graph<W>(graph: &[Vec<(usize, W)>], opt: &DotOptions) -> String
+where
+    W: Display,
+{
+    let edges = internal_edges(
+        graph
+            .iter()
+            .enumerate()
+            .flat_map(|(v, adj)| adj.iter().map(move |(to, w)| (v, *to, Some(w.to_string())))),
+        opt.directed,
+    );
+    internal_dot(graph.len(), &edges, opt)
+}
+
+///Lcaに登録した木をDOT形式の文字列にする。(calc済みである必要がある)
+///
+///directedがtrueなら親から子への辺として出力する。
+pub fn dot_tree(lca: &Lca, opt: &DotOptions) -> String {
+    let edges = (0..lca.len())
+        .filter_map(|v| lca.get_tree_parent(v).map(|p| (p, v, None)))
+        .collect::<Vec<_>>();
+    internal_dot(lca.len(), &edges, opt)
+}
+
+///フローネットワークをDOT形式の文字列にする。辺のラベルは"流量/容量"になり、流れていない辺は灰色になる。
+///
+///opt.directedは無視する。
+pub fn dot_flow(graph: &Dinic, opt: &DotOptions) -> String {
+    let edges = graph
+        .edges()
+        .into_iter()
+        .map(|(from, to, cap, flow)| (from, to, Some(format!("{}/{}", flow, cap)), flow == 0))
+        .collect::<Vec<_>>();
+    let opt = DotOptions {
+        directed: true,
+        ..opt.clone()
+    };
+    internal_dot_with_dim(graph.len(), &edges, &opt)
+}
+
+///DOT形式の文字列を標準エラー出力に書き出す。
+pub fn dot_to_stderr(dot: &str) {
+    eprintln!("{}", dot);
+}
+
+///DOT形式の文字列をファイルに書き出す。
+pub fn dot_to_file<P>(dot: &str, path: P) -> std::io::Result<()>
+where
+    P: AsRef<Path>,
+{
+    std::fs::write(path, dot)
+}
+
+//無向グラフなら両方向にある辺を1本にまとめる。
+fn internal_edges<I>(edges: I, directed: bool) -> Vec<(usize, usize, Option<String>)>
+where
+    I: Iterator<Item = (usize, usize, Option<String>)>,
+{
+    if directed {
+        return edges.collect();
+    }
+    //自己ループは隣接リストに2回現れるので、1回おきに採用する。
+    let mut odd_loop = HashSet::new();
+    let mut r = vec![];
+    for (u, v, label) in edges {
+        if u == v {
+            if !odd_loop.insert(u) {
+                odd_loop.remove(&u);
+                r.push((u, v, label));
+            }
+        } else if u < v {
+            r.push((u, v, label));
+        }
+    }
+    r
+}
+
+fn internal_dot(n: usize, edges: &[(usize, usize, Option<String>)], opt: &DotOptions) -> String {
+    let edges = edges
+        .iter()
+        .map(|(u, v, label)| (*u, *v, label.clone(), false))
+        .collect::<Vec<_>>();
+    internal_dot_with_dim(n, &edges, opt)
+}
+
+fn internal_dot_with_dim(
+    n: usize,
+    edges: &[(usize, usize, Option<String>, bool)],
+    opt: &DotOptions,
+) -> String {
+    let on_path = opt.path.iter().copied().collect::<HashSet<_>>();
+    let mut path_edges = HashSet::new();
+    for w in opt.path.windows(2) {
+        path_edges.insert((w[0], w[1]));
+        if !opt.directed {
+            path_edges.insert((w[1], w[0]));
+        }
+    }
+
+    let mut s = String::new();
+    let arrow = if opt.directed { "->" } else { "--" };
+    writeln!(s, "{} {{", if opt.directed { "digraph" } else { "graph" }).unwrap();
+    for v in 0..n {
+        let mut attrs = vec![];
+        if let Some(labels) = &opt.labels {
+            attrs.push(format!("label=\"{}\"", escape(&labels[v])));
+        }
+        if let Some(components) = &opt.components {
+            let color = COLORS[components[v] % COLORS.len()];
+            attrs.push(format!("style=filled, fillcolor={}", color));
+        }
+        if on_path.contains(&v) {
+            attrs.push("color=red, penwidth=2".to_string());
+        }
+        writeln!(s, "    {}{};", v, internal_attrs(&attrs)).unwrap();
+    }
+    for (u, v, label, dim) in edges {
+        let mut attrs = vec![];
+        if let Some(label) = label {
+            attrs.push(format!("label=\"{}\"", escape(label)));
+        }
+        if path_edges.remove(&(*u, *v)) {
+            if !opt.directed {
+                path_edges.remove(&(*v, *u));
+            }
+            attrs.push("color=red, penwidth=2".to_string());
+        } else if *dim {
+            attrs.push("color=gray".to_string());
+        }
+        writeln!(s, "    {} {} {}{};", u, arrow, v, internal_attrs(&attrs)).unwrap();
+    }
+    s.push('}');
+    s
+}
+
+fn internal_attrs(attrs: &[String]) -> String {
+    if attrs.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", attrs.join(", "))
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dot_works() {
+        let graph = vec![vec![1, 2], vec![0, 2], vec![2, 0, 2, 1], vec![]];
+        let opt = DotOptions {
+            path: vec![0, 2],
+            components: Some(vec![0, 0, 0, 1]),
+            ..Default::default()
+        };
+        assert_eq!(
+            dot_graph(&graph, &opt),
+            "graph {
+    0 [style=filled, fillcolor=lightblue, color=red, penwidth=2];
+    1 [style=filled, fillcolor=lightblue];
+    2 [style=filled, fillcolor=lightblue, color=red, penwidth=2];
+    3 [style=filled, fillcolor=lightpink];
+    0 -- 1;
+    0 -- 2 [color=red, penwidth=2];
+    1 -- 2;
+    2 -- 2;
+}"
+        );
+
+        let graph = vec![vec![(1, -3)], vec![]];
+        let opt = DotOptions {
+            directed: true,
+            labels: Some(vec!["s".to_string(), "\"t\"".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            dot_weighted_graph(&graph, &opt),
+            "digraph {
+    0 [label=\"s\"];
+    1 [label=\"\\\"t\\\"\"];
+    0 -> 1 [label=\"-3\"];
+}"
+        );
+
+        let mut lca = Lca::new(3, 0);
+        lca.add_edge(0, 1);
+        lca.add_edge(1, 2);
+        lca.calc();
+        let dot = dot_tree(&lca, &DotOptions::default());
+        assert!(dot.contains("0 -- 1;") && dot.contains("1 -- 2;"));
+
+        //根から到達できない頂点には辺を出力しない。
+        let mut lca = Lca::new(5, 0);
+        lca.add_edge(0, 1);
+        lca.add_edge(3, 4);
+        lca.calc();
+        let dot = dot_tree(&lca, &DotOptions::default());
+        assert!(dot.contains("0 -- 1;"));
+        assert_eq!(dot.matches("--").count(), 1);
+
+        let mut flow = Dinic::new(3);
+        flow.add_edge(0, 1, 2);
+        flow.add_edge(0, 2, 1);
+        flow.flow(0, 1);
+        let dot = dot_flow(&flow, &DotOptions::default());
+        assert!(dot.contains("0 -> 1 [label=\"2/2\"];"));
+        assert!(dot.contains("0 -> 2 [label=\"0/1\", color=gray];"));
+    }
+}
@@ -0,0 +1,155 @@
+///Functional Graph(各頂点の出次数がちょうど1のグラフ)
+///
+///next:各頂点の行き先
+pub struct FunctionalGraph {
+    cycles: Vec<Vec<usize>>,
+    cycle_id: Vec<usize>,
+    cycle_pos: Vec<usize>,
+    dist: Vec<usize>,
+    root: Vec<usize>,
+    parent: Vec<Vec<usize>>,
+}
+
+impl FunctionalGraph {
+    pub fn new(next: &[usize]) -> FunctionalGraph {
+        let n = next.len();
+        let mut cycles = vec![];
+        let mut cycle_id = vec![0; n];
+        let mut cycle_pos = vec![0; n];
+        let mut dist = vec![0; n];
+        let mut root = vec![0; n];
+        //0:未訪問, 1:探索中, 2:確定済み
+        let mut state = vec![0_u8; n];
+        let mut path_idx = vec![0; n];
+        let mut path = vec![];
+
+        for s in 0..n {
+            if state[s] != 0 {
+                continue;
+            }
+            let mut v = s;
+            while state[v] == 0 {
+                state[v] = 1;
+                path_idx[v] = path.len();
+                path.push(v);
+                v = next[v];
+            }
+            let mut tail_len = path.len();
+            if state[v] == 1 {
+                tail_len = path_idx[v];
+                let id = cycles.len();
+                let cycle = path[tail_len..].to_vec();
+                for (i, &u) in cycle.iter().enumerate() {
+                    state[u] = 2;
+                    cycle_id[u] = id;
+                    cycle_pos[u] = i;
+                    root[u] = u;
+                }
+                cycles.push(cycle);
+            }
+            for &u in path[..tail_len].iter().rev() {
+                state[u] = 2;
+                dist[u] = dist[next[u]] + 1;
+                root[u] = root[next[u]];
+                cycle_id[u] = cycle_id[next[u]];
+            }
+            path.clear();
+        }
+
+        let max_dist = dist.iter().copied().max().unwrap_or(0);
+        let mut bit_size = 1;
+        while 1 << bit_size <= max_dist {
+            bit_size += 1;
+        }
+        let mut parent = vec![next.to_vec()];
+        for i in 0..bit_size - 1 {
+            let p = (0..n).map(|v| parent[i][parent[i][v]]).collect();
+            parent.push(p);
+        }
+
+        FunctionalGraph {
+            cycles,
+            cycle_id,
+            cycle_pos,
+            dist,
+            root,
+            parent,
+        }
+    }
+
+    ///閉路の一覧(各閉路は辿る順に並ぶ)
+    pub fn cycles(&self) -> &[Vec<usize>] {
+        &self.cycles
+    }
+
+    ///頂点vから辿り着く閉路の番号
+    pub fn cycle_id(&self, v: usize) -> usize {
+        self.cycle_id[v]
+    }
+
+    ///頂点vから辿り着く閉路の長さ
+    pub fn cycle_len(&self, v: usize) -> usize {
+        self.cycles[self.cycle_id[v]].len()
+    }
+
+    ///頂点vから閉路に入るまでの距離(閉路上の頂点なら0)
+    pub fn dist_to_cycle(&self, v: usize) -> usize {
+        self.dist[v]
+    }
+
+    ///頂点vから辿って最初に到達する閉路上の頂点
+    pub fn root(&self, v: usize) -> usize {
+        self.root[v]
+    }
+
+    pub fn is_on_cycle(&self, v: usize) -> bool {
+        self.dist[v] == 0
+    }
+
+    ///頂点vからk回移動した先の頂点
+    pub fn kth(&self, mut v: usize, k: u64) -> usize {
+        if k >= self.dist[v] as u64 {
+            let rem = k - self.dist[v] as u64;
+            let r = self.root[v];
+            let cycle = &self.cycles[self.cycle_id[r]];
+            let len = cycle.len() as u64;
+            return cycle[((self.cycle_pos[r] as u64 + rem % len) % len) as usize];
+        }
+        for (i, p) in self.parent.iter().enumerate() {
+            if (k >> i) & 1 == 1 {
+                v = p[v];
+            }
+        }
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn functional_graph_works() {
+        //0->1->2->3->1, 4->0, 5->5, 6->4
+        let next = vec![1, 2, 3, 1, 0, 5, 4];
+        let fg = FunctionalGraph::new(&next);
+        assert_eq!(fg.cycles().len(), 2);
+        assert_eq!(fg.cycle_len(6), 3);
+        assert_eq!(fg.cycle_len(5), 1);
+        assert_ne!(fg.cycle_id(6), fg.cycle_id(5));
+        assert_eq!(fg.dist_to_cycle(6), 3);
+        assert_eq!(fg.dist_to_cycle(2), 0);
+        assert_eq!(fg.root(6), 1);
+        assert!(fg.is_on_cycle(3));
+        assert!(!fg.is_on_cycle(0));
+
+        for s in 0..next.len() {
+            let mut v = s;
+            for k in 0..20 {
+                assert_eq!(fg.kth(s, k), v);
+                v = next[v];
+            }
+        }
+        assert_eq!(fg.kth(6, 1_000_000_000_000_000_000), 2);
+    }
+}
@@ -0,0 +1,229 @@
+use crate::matrix::Matrix;
+use crate::SmallRng;
+use std::collections::VecDeque;
+
+///一般グラフの最大マッチング(Edmondsの花アルゴリズム、O(n^3))
+///
+///graph:無向グラフの隣接リスト
+///
+///各頂点のマッチ相手(マッチしていなければNone)を返す。
+pub fn general_matching(graph: &[Vec<usize>]) -> Vec<Option<usize>> {
+    let n = graph.len();
+    let mut mate = vec![None; n];
+    //貪欲に初期解を作っておく。
+    for v in 0..n {
+        if mate[v].is_some() {
+            continue;
+        }
+        if let Some(&to) = graph[v].iter().find(|&&to| to != v && mate[to].is_none()) {
+            mate[v] = Some(to);
+            mate[to] = Some(v);
+        }
+    }
+
+    let mut blossom = Blossom {
+        graph,
+        mate,
+        parent: vec![None; n],
+        base: (0..n).collect(),
+        used: vec![false; n],
+        in_blossom: vec![false; n],
+    };
+    for root in 0..n {
+        if blossom.mate[root].is_some() {
+            continue;
+        }
+        let mut v = blossom.find_path(root);
+        //増加路に沿ってマッチングを反転する。
+        while let Some(x) = v {
+            let px = blossom.parent[x].unwrap();
+            let next = blossom.mate[px];
+            blossom.mate[x] = Some(px);
+            blossom.mate[px] = Some(x);
+            v = next;
+        }
+    }
+    blossom.mate
+}
+
+///最大マッチングの大きさ(Tutte行列の階数を用いた乱択アルゴリズム、O(n^3))
+///
+///誤った値(真の値より小さい値)を返す確率はn/998244353以下。
+pub fn matching_size_tutte<R>(graph: &[Vec<usize>], rng: &mut R) -> usize
+where
+    R: SmallRng,
+{
+    const MOD: u64 = 998_244_353;
+    let n = graph.len();
+    if n == 0 {
+        return 0;
+    }
+    let mut m = Matrix::new(n, n, 0_u64);
+    for (v, adj) in graph.iter().enumerate() {
+        for &to in adj {
+            if v < to {
+                let x = rng.range_u64(1..MOD);
+                m[v][to] = x;
+                m[to][v] = MOD - x;
+            }
+        }
+    }
+
+    let mut rank = 0;
+    for col in 0..n {
+        let pivot = match (rank..n).find(|&i| m[i][col] != 0) {
+            Some(p) => p,
+            None => continue,
+        };
+        m.chrow(rank, pivot);
+        let inv = mod_pow(m[rank][col], MOD - 2, MOD);
+        for i in rank + 1..n {
+            if m[i][col] == 0 {
+                continue;
+            }
+            let f = m[i][col] * inv % MOD;
+            for j in col..n {
+                m[i][j] = (m[i][j] + MOD - f * m[rank][j] % MOD) % MOD;
+            }
+        }
+        rank += 1;
+    }
+    rank / 2
+}
+
+fn mod_pow(mut x: u64, mut p: u64, m: u64) -> u64 {
+    let mut r = 1;
+    while p > 0 {
+        if p & 1 == 1 {
+            r = r * x % m;
+        }
+        x = x * x % m;
+        p >>= 1;
+    }
+    r
+}
+
+struct Blossom<'a> {
+    graph: &'a [Vec<usize>],
+    mate: Vec<Option<usize>>,
+    parent: Vec<Option<usize>>,
+    base: Vec<usize>,
+    used: Vec<bool>,
+    in_blossom: Vec<bool>,
+}
+
+impl<'a> Blossom<'a> {
+    //交互木の上でa,bの(縮約後の)最小共通祖先を求める。
+    fn lca(&self, mut a: usize, mut b: usize) -> usize {
+        let mut visited = vec![false; self.graph.len()];
+        loop {
+            a = self.base[a];
+            visited[a] = true;
+            match self.mate[a] {
+                Some(m) => a = self.parent[m].unwrap(),
+                None => break,
+            }
+        }
+        loop {
+            b = self.base[b];
+            if visited[b] {
+                return b;
+            }
+            b = self.parent[self.mate[b].unwrap()].unwrap();
+        }
+    }
+
+    fn mark_path(&mut self, mut v: usize, b: usize, mut child: usize) {
+        while self.base[v] != b {
+            let m = self.mate[v].unwrap();
+            self.in_blossom[self.base[v]] = true;
+            self.in_blossom[self.base[m]] = true;
+            self.parent[v] = Some(child);
+            child = m;
+            v = self.parent[m].unwrap();
+        }
+    }
+
+    //rootからの増加路を探し、その終点を返す。
+    fn find_path(&mut self, root: usize) -> Option<usize> {
+        let n = self.graph.len();
+        self.used.iter_mut().for_each(|x| *x = false);
+        self.parent.iter_mut().for_each(|x| *x = None);
+        for (i, b) in self.base.iter_mut().enumerate() {
+            *b = i;
+        }
+        self.used[root] = true;
+        let mut queue = VecDeque::new();
+        queue.push_back(root);
+        while let Some(v) = queue.pop_front() {
+            for &to in &self.graph[v] {
+                if self.base[v] == self.base[to] || self.mate[v] == Some(to) {
+                    continue;
+                }
+                let odd_cycle =
+                    to == root || matches!(self.mate[to], Some(m) if self.parent[m].is_some());
+                if odd_cycle {
+                    //花を縮約する。
+                    let cur_base = self.lca(v, to);
+                    self.in_blossom.iter_mut().for_each(|x| *x = false);
+                    self.mark_path(v, cur_base, to);
+                    self.mark_path(to, cur_base, v);
+                    for i in 0..n {
+                        if self.in_blossom[self.base[i]] {
+                            self.base[i] = cur_base;
+                            if !self.used[i] {
+                                self.used[i] = true;
+                                queue.push_back(i);
+                            }
+                        }
+                    }
+                } else if self.parent[to].is_none() {
+                    self.parent[to] = Some(v);
+                    match self.mate[to] {
+                        None => return Some(to),
+                        Some(m) => {
+                            self.used[m] = true;
+                            queue.push_back(m);
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift;
+
+    fn undirected(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+        let mut graph = vec![vec![]; n];
+        for &(u, v) in edges {
+            graph[u].push(v);
+            graph[v].push(u);
+        }
+        graph
+    }
+
+    #[test]
+    fn general_matching_works() {
+        //5頂点の奇閉路と、そこにぶら下がる頂点
+        let graph = undirected(7, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (2, 5), (4, 6)]);
+        let mate = general_matching(&graph);
+        assert_eq!(mate.iter().flatten().count(), 6);
+        for (v, m) in mate.iter().enumerate() {
+            if let Some(m) = *m {
+                assert_eq!(mate[m], Some(v));
+                assert!(graph[v].contains(&m));
+            }
+        }
+
+        let mut rng = XorShift::default();
+        assert_eq!(matching_size_tutte(&graph, &mut rng), 3);
+        let graph = undirected(3, &[(0, 1), (1, 2), (2, 0)]);
+        assert_eq!(general_matching(&graph).iter().flatten().count(), 2);
+        assert_eq!(matching_size_tutte(&graph, &mut rng), 1);
+    }
+}
@@ -1,5 +1,59 @@
+use crate::csr::Csr;
+use crate::matrix::Matrix;
+use crate::ordfloat::OrdFloat;
 use std::cmp;
-use std::collections::BinaryHeap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::ops::Sub;
+
+///最短経路計算で使うコストの型
+pub trait Cost: Copy + Ord {
+    fn zero() -> Self;
+    fn plus(self, other: Self) -> Self;
+}
+
+macro_rules! cost_impl {
+    ($($t:ty),*) => {
+        $(
+            impl Cost for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn plus(self, other: Self) -> Self {
+                    self + other
+                }
+            }
+        )*
+    };
+}
+
+cost_impl!(u32, u64, u128, usize, i32, i64, i128, isize);
+
+impl Cost for OrdFloat {
+    fn zero() -> Self {
+        OrdFloat(0.0)
+    }
+
+    fn plus(self, other: Self) -> Self {
+        OrdFloat(self.0 + other.0)
+    }
+}
+
+///(コスト, 辺の本数)のように辞書順で比較するコスト
+impl<A, B> Cost for (A, B)
+where
+    A: Cost,
+    B: Cost,
+{
+    fn zero() -> Self {
+        (A::zero(), B::zero())
+    }
+
+    fn plus(self, other: Self) -> Self {
+        (self.0.plus(other.0), self.1.plus(other.1))
+    }
+}
 
 ///最短経路計算(ベルマンフォード法)
 ///
@@ -58,6 +112,224 @@ pub fn bellman_ford(graph: &Vec<Vec<(usize, isize)>>, dist: &mut [Option<isize>]
     neg_flag
 }
 
+///ベルマンフォード法で求めた距離
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Distance<T> {
+    Unreachable,
+    Finite(T),
+    ///負の閉路を経由していくらでも小さくできる
+    NegativeInfinity,
+}
+
+///ベルマンフォード法の計算結果
+pub struct BellmanFord<T> {
+    pub dist: Vec<Distance<T>>,
+    ///最短経路木における直前の頂点(距離が有限の頂点のみ意味を持つ)
+    pub prev: Vec<Option<usize>>,
+    ///始点から到達できる負の閉路(辺の向きの順)があれば、その一つ
+    pub negative_cycle: Option<Vec<usize>>,
+}
+
+impl<T> BellmanFord<T> {
+    ///始点からtまでの最短経路の頂点列を返す。距離が有限でない場合はNoneを返す。
+    pub fn restore_path(&self, t: usize) -> Option<Vec<usize>> {
+        match self.dist[t] {
+            Distance::Finite(_) => {}
+            _ => return None,
+        }
+        let mut r = vec![t];
+        let mut v = t;
+        while let Some(p) = self.prev[v] {
+            r.push(p);
+            v = p;
+        }
+        r.reverse();
+        Some(r)
+    }
+}
+
+///最短経路計算(ベルマンフォード法、負の閉路の検出つき)
+///
+///graph:グラフ情報
+///
+///start:始点
+pub fn bellman_ford_from<T>(graph: &Csr<T>, start: usize) -> BellmanFord<T>
+where
+    T: Cost,
+{
+    let n = graph.len();
+    let mut dist: Vec<Option<T>> = vec![None; n];
+    let mut prev = vec![None; n];
+    dist[start] = Some(T::zero());
+
+    //n回目の緩和で更新された頂点は負の閉路から到達できる。
+    let mut last_updated = vec![];
+    for round in 0..n {
+        last_updated.clear();
+        for from in 0..n {
+            let d = match dist[from] {
+                Some(d) => d,
+                None => continue,
+            };
+            for &(to, cost) in graph.adj(from) {
+                let nd = d.plus(cost);
+                if !matches!(dist[to], Some(x) if x <= nd) {
+                    dist[to] = Some(nd);
+                    prev[to] = Some(from);
+                    if round == n - 1 {
+                        last_updated.push(to);
+                    }
+                }
+            }
+        }
+    }
+
+    let negative_cycle = last_updated
+        .first()
+        .map(|&v| internal_extract_cycle(&prev, v));
+    internal_bellman_ford_result(graph, dist, prev, &last_updated, negative_cycle)
+}
+
+///最短経路計算(SPFA)
+///
+///結果はbellman_ford_fromと同じ。負の閉路が見つかった場合はbellman_ford_fromで計算し直す。
+pub fn spfa<T>(graph: &Csr<T>, start: usize) -> BellmanFord<T>
+where
+    T: Cost,
+{
+    let n = graph.len();
+    let mut dist: Vec<Option<T>> = vec![None; n];
+    let mut prev = vec![None; n];
+    //始点からの経路の辺数(n以上になれば負の閉路がある)
+    let mut len = vec![0; n];
+    let mut in_queue = vec![false; n];
+    let mut queue = VecDeque::new();
+    dist[start] = Some(T::zero());
+    queue.push_back(start);
+    in_queue[start] = true;
+
+    while let Some(v) = queue.pop_front() {
+        in_queue[v] = false;
+        let d = dist[v].unwrap();
+        for &(to, cost) in graph.adj(v) {
+            let nd = d.plus(cost);
+            if !matches!(dist[to], Some(x) if x <= nd) {
+                dist[to] = Some(nd);
+                prev[to] = Some(v);
+                len[to] = len[v] + 1;
+                if len[to] >= n {
+                    return bellman_ford_from(graph, start);
+                }
+                if !in_queue[to] {
+                    in_queue[to] = true;
+                    queue.push_back(to);
+                }
+            }
+        }
+    }
+    internal_bellman_ford_result(graph, dist, prev, &[], None)
+}
+
+//prevを辿って閉路に入り、その閉路を辺の向きの順に返す。
+fn internal_extract_cycle(prev: &[Option<usize>], mut v: usize) -> Vec<usize> {
+    for _ in 0..prev.len() {
+        v = prev[v].unwrap();
+    }
+    let mut cycle = vec![v];
+    let mut u = prev[v].unwrap();
+    while u != v {
+        cycle.push(u);
+        u = prev[u].unwrap();
+    }
+    cycle.reverse();
+    cycle
+}
+
+fn internal_bellman_ford_result<T>(
+    graph: &Csr<T>,
+    dist: Vec<Option<T>>,
+    prev: Vec<Option<usize>>,
+    negative: &[usize],
+    negative_cycle: Option<Vec<usize>>,
+) -> BellmanFord<T>
+where
+    T: Cost,
+{
+    let mut dist = dist
+        .into_iter()
+        .map(|d| match d {
+            Some(d) => Distance::Finite(d),
+            None => Distance::Unreachable,
+        })
+        .collect::<Vec<_>>();
+    let mut stack = negative.to_vec();
+    for &v in negative {
+        dist[v] = Distance::NegativeInfinity;
+    }
+    while let Some(v) = stack.pop() {
+        for &(to, _) in graph.adj(v) {
+            if dist[to] != Distance::NegativeInfinity {
+                dist[to] = Distance::NegativeInfinity;
+                stack.push(to);
+            }
+        }
+    }
+    BellmanFord {
+        dist,
+        prev,
+        negative_cycle,
+    }
+}
+
+///全点対最短経路(Johnson法)
+///
+///負の辺を含む疎なグラフでO(nm log n)で計算する。
+///
+///各頂点対の最短距離(到達できない場合はNone)を返す。負の閉路がある場合はその頂点列をErrで返す。
+pub fn johnson<T>(graph: &Csr<T>) -> Result<Vec<Vec<Option<T>>>, Vec<usize>>
+where
+    T: Cost + Sub<Output = T>,
+{
+    let n = graph.len();
+    let mut edges = vec![];
+    for from in 0..n {
+        for &(to, cost) in graph.adj(from) {
+            edges.push((from, to, cost));
+        }
+    }
+    //頂点nから全頂点へコスト0の辺を張り、ポテンシャルを求める。
+    let mut extended = edges.clone();
+    extended.extend((0..n).map(|v| (n, v, T::zero())));
+    let bf = bellman_ford_from(&Csr::directed(n + 1, &extended), n);
+    if let Some(cycle) = bf.negative_cycle {
+        return Err(cycle);
+    }
+    let h = bf
+        .dist
+        .iter()
+        .take(n)
+        .map(|d| match *d {
+            Distance::Finite(d) => d,
+            _ => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+
+    for e in edges.iter_mut() {
+        e.2 = e.2.plus(h[e.0]) - h[e.1];
+    }
+    let reweighted = Csr::directed(n, &edges);
+    Ok((0..n)
+        .map(|s| {
+            dijkstra_paths(&reweighted, &[s])
+                .dist
+                .into_iter()
+                .enumerate()
+                .map(|(t, d)| d.map(|d| d.plus(h[t]) - h[s]))
+                .collect()
+        })
+        .collect())
+}
+
 ///最短経路計算(ダイクストラ法)
 ///
 ///最短経路が格納された配列を返す。(到達できない場合はisize::max_value()を返す。)
@@ -68,25 +340,119 @@ pub fn bellman_ford(graph: &Vec<Vec<(usize, isize)>>, dist: &mut [Option<isize>]
 
 #[allow(clippy::ptr_arg)]
 pub fn dijkstra(graph: &Vec<Vec<(usize, isize)>>, start: usize) -> Vec<isize> {
-    let mut heap = BinaryHeap::new();
-    let mut dist = vec![isize::max_value(); graph.len()];
-    dist[start] = 0;
+    dijkstra_csr(&Csr::from_adjacency(graph), start)
+}
+
+///最短経路計算(ダイクストラ法、CSR形式のグラフ)
+///
+///最短経路が格納された配列を返す。(到達できない場合はisize::max_value()を返す。)
+pub fn dijkstra_csr(graph: &Csr<isize>, start: usize) -> Vec<isize> {
+    dijkstra_paths(graph, &[start])
+        .dist
+        .into_iter()
+        .map(|d| d.unwrap_or(isize::MAX))
+        .collect()
+}
+
+///最短経路の計算結果
+pub struct ShortestPath<T> {
+    ///各頂点への最短距離(到達できない場合はNone)
+    pub dist: Vec<Option<T>>,
+    ///最短経路木における直前の頂点(始点と到達できない頂点はNone)
+    pub prev: Vec<Option<usize>>,
+}
+
+impl<T> ShortestPath<T> {
+    ///始点からtまでの最短経路の頂点列を返す。到達できない場合はNoneを返す。
+    pub fn restore_path(&self, t: usize) -> Option<Vec<usize>> {
+        self.dist[t].as_ref()?;
+        let mut r = vec![t];
+        let mut v = t;
+        while let Some(p) = self.prev[v] {
+            r.push(p);
+            v = p;
+        }
+        r.reverse();
+        Some(r)
+    }
+}
+
+///最短経路計算(ダイクストラ法、コストの型は任意)
+///
+///graph:グラフ情報(コストは非負)
+///
+///starts:始点の集合(複数指定すると最も近い始点からの距離になる。同じ頂点を複数回指定しても1回と同じ)
+pub fn dijkstra_paths<T>(graph: &Csr<T>, starts: &[usize]) -> ShortestPath<T>
+where
+    T: Cost,
+{
+    internal_dijkstra(graph, starts, None).0
+}
+
+///最短経路計算(ダイクストラ法)を行い、各頂点への最短経路の本数をmodulusで割った余りも返す。
+///
+///コストは正である必要がある。
+///
+///経路はいずれかの始点から出発するものを数える。(同じ始点を複数回指定しても1回と同じ)
+pub fn dijkstra_count<T>(
+    graph: &Csr<T>,
+    starts: &[usize],
+    modulus: u64,
+) -> (ShortestPath<T>, Vec<u64>)
+where
+    T: Cost,
+{
+    internal_dijkstra(graph, starts, Some(modulus))
+}
 
-    heap.push((0_isize, start));
+fn internal_dijkstra<T>(
+    graph: &Csr<T>,
+    starts: &[usize],
+    modulus: Option<u64>,
+) -> (ShortestPath<T>, Vec<u64>)
+where
+    T: Cost,
+{
+    let n = graph.len();
+    let mut dist: Vec<Option<T>> = vec![None; n];
+    let mut prev = vec![None; n];
+    let mut count = vec![0; if modulus.is_some() { n } else { 0 }];
+    let mut heap = BinaryHeap::new();
+    for &s in starts {
+        if dist[s].is_none() {
+            dist[s] = Some(T::zero());
+            heap.push(Reverse((T::zero(), s)));
+            if let Some(m) = modulus {
+                count[s] = 1 % m;
+            }
+        }
+    }
 
-    while !heap.is_empty() {
-        let e = heap.pop().unwrap();
-        if e.0.wrapping_neg() > dist[e.1] {
+    while let Some(Reverse((d, v))) = heap.pop() {
+        if dist[v] != Some(d) {
             continue;
         }
-        for next_e in graph[e.1].iter() {
-            if dist[next_e.0] > next_e.1 + dist[e.1] {
-                dist[next_e.0] = next_e.1 + dist[e.1];
-                heap.push(((dist[e.1] + next_e.1).wrapping_neg(), next_e.0));
+        for &(to, cost) in graph.adj(v) {
+            let nd = d.plus(cost);
+            match dist[to] {
+                Some(x) if x < nd => {}
+                Some(x) if x == nd => {
+                    if let Some(m) = modulus {
+                        count[to] = (count[to] + count[v]) % m;
+                    }
+                }
+                _ => {
+                    dist[to] = Some(nd);
+                    prev[to] = Some(v);
+                    if let Some(m) = modulus {
+                        count[to] = count[v] % m;
+                    }
+                    heap.push(Reverse((nd, to)));
+                }
             }
         }
     }
-    dist
+    (ShortestPath { dist, prev }, count)
 }
 
 ///最短経路計算(ワーシャルフロイド法)
@@ -99,3 +465,372 @@ pub fn warshall_floyd(graph: &mut [&mut [isize]]) {
         }
     }
 }
+
+///全点対最短経路(ワーシャルフロイド法、経路復元・負の閉路検出つき)
+pub struct WarshallFloyd<T> {
+    dist: Vec<Vec<Option<T>>>,
+    next: Vec<Vec<usize>>,
+    negative_infinity: Vec<Vec<bool>>,
+}
+
+impl<T> WarshallFloyd<T>
+where
+    T: Cost,
+{
+    ///graph\[i\]\[j\]:頂点iから頂点jへの辺のコスト(辺がなければNone)
+    pub fn new(graph: Vec<Vec<Option<T>>>) -> WarshallFloyd<T> {
+        let n = graph.len();
+        let mut dist = graph;
+        let mut next = vec![vec![usize::MAX; n]; n];
+        let mut negative_infinity = vec![vec![false; n]; n];
+        for i in 0..n {
+            for j in 0..n {
+                if dist[i][j].is_some() {
+                    next[i][j] = j;
+                }
+            }
+            if matches!(dist[i][i], Some(x) if x < T::zero()) {
+                negative_infinity[i][i] = true;
+            } else {
+                dist[i][i] = Some(T::zero());
+                next[i][i] = i;
+            }
+        }
+
+        for k in 0..n {
+            for i in 0..n {
+                let d_ik = match dist[i][k] {
+                    Some(d) => d,
+                    None => continue,
+                };
+                for j in 0..n {
+                    let d_kj = match dist[k][j] {
+                        Some(d) => d,
+                        None => continue,
+                    };
+                    //負の閉路を経由する距離には足し算をしない。(桁あふれを防ぐ)
+                    if negative_infinity[i][k] || negative_infinity[k][j] || negative_infinity[k][k]
+                    {
+                        negative_infinity[i][j] = true;
+                        dist[i][j].get_or_insert(d_ik);
+                        continue;
+                    }
+                    let nd = d_ik.plus(d_kj);
+                    if !matches!(dist[i][j], Some(x) if x <= nd) {
+                        dist[i][j] = Some(nd);
+                        next[i][j] = next[i][k];
+                        if i == j && nd < T::zero() {
+                            negative_infinity[i][i] = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut wf = WarshallFloyd {
+            dist,
+            next,
+            negative_infinity,
+        };
+        wf.update_negative_infinity();
+        wf
+    }
+
+    ///隣接行列から構築する。inf以上の要素は辺がないものとして扱う。
+    pub fn from_matrix(graph: &Matrix<T>, inf: T) -> WarshallFloyd<T> {
+        WarshallFloyd::new(
+            graph
+                .iter()
+                .map(|v| {
+                    v.iter()
+                        .map(|&x| if x >= inf { None } else { Some(x) })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    ///頂点iから頂点jへの最短距離
+    pub fn dist(&self, i: usize, j: usize) -> Distance<T> {
+        if self.negative_infinity[i][j] {
+            return Distance::NegativeInfinity;
+        }
+        match self.dist[i][j] {
+            Some(d) => Distance::Finite(d),
+            None => Distance::Unreachable,
+        }
+    }
+
+    pub fn has_negative_cycle(&self) -> bool {
+        !self.negative_cycle_vertices().is_empty()
+    }
+
+    ///負の閉路を通って自分自身に戻れる頂点(負の閉路と同じ強連結成分にある頂点)を返す。
+    pub fn negative_cycle_vertices(&self) -> Vec<usize> {
+        (0..self.dist.len())
+            .filter(|&v| self.negative_infinity[v][v])
+            .collect()
+    }
+
+    ///頂点iから頂点jへの最短経路の頂点列を返す。距離が有限でない場合はNoneを返す。
+    pub fn path(&self, i: usize, j: usize) -> Option<Vec<usize>> {
+        match self.dist(i, j) {
+            Distance::Finite(_) => {}
+            _ => return None,
+        }
+        let mut r = vec![i];
+        let mut v = i;
+        while v != j {
+            v = self.next[v][j];
+            r.push(v);
+        }
+        Some(r)
+    }
+
+    ///辺u->v(コストw)を追加し、O(n^2)で最短距離を更新する。
+    pub fn add_edge(&mut self, u: usize, v: usize, w: T) {
+        let n = self.dist.len();
+        //追加した辺を含む閉路がいくらでも小さくできるか
+        let cycle = self.negative_infinity[v][u]
+            || matches!(self.dist[v][u], Some(d) if d.plus(w) < T::zero());
+        for i in 0..n {
+            let d_iu = match self.dist[i][u] {
+                Some(d) => d,
+                None => continue,
+            };
+            for j in 0..n {
+                let d_vj = match self.dist[v][j] {
+                    Some(d) => d,
+                    None => continue,
+                };
+                if cycle || self.negative_infinity[i][u] || self.negative_infinity[v][j] {
+                    self.negative_infinity[i][j] = true;
+                    self.dist[i][j].get_or_insert(d_iu);
+                    continue;
+                }
+                let nd = d_iu.plus(w).plus(d_vj);
+                if !matches!(self.dist[i][j], Some(x) if x <= nd) {
+                    self.dist[i][j] = Some(nd);
+                    self.next[i][j] = if i == u { v } else { self.next[i][u] };
+                }
+            }
+        }
+    }
+
+    //負の閉路を経由できる頂点対に印をつける。
+    fn update_negative_infinity(&mut self) {
+        let negative = self.negative_cycle_vertices();
+        let n = self.dist.len();
+        for i in 0..n {
+            for j in 0..n {
+                if negative
+                    .iter()
+                    .any(|&k| self.dist[i][k].is_some() && self.dist[k][j].is_some())
+                {
+                    self.negative_infinity[i][j] = true;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dijkstra_count_works() {
+        let edges = vec![(0, 1, 1_u64), (0, 2, 1), (1, 3, 1), (2, 3, 1), (3, 4, 2)];
+        let graph = Csr::undirected(6, &edges);
+        let (sp, count) = dijkstra_count(&graph, &[0], 1_000_000_007);
+        assert_eq!(
+            sp.dist,
+            vec![Some(0), Some(1), Some(1), Some(2), Some(4), None]
+        );
+        assert_eq!(count, vec![1, 1, 1, 2, 2, 0]);
+        assert_eq!(sp.restore_path(4), Some(vec![0, 1, 3, 4]));
+        assert_eq!(sp.restore_path(0), Some(vec![0]));
+        assert_eq!(sp.restore_path(5), None);
+        assert_eq!(dijkstra_count(&graph, &[0], 2).1, vec![1, 1, 1, 0, 0, 0]);
+        assert_eq!(dijkstra_count(&graph, &[0, 0], 1_000_000_007).1, count);
+
+        let (sp, count) = dijkstra_count(&graph, &[0, 3, 3], 1_000_000_007);
+        assert_eq!(
+            sp.dist,
+            vec![Some(0), Some(1), Some(1), Some(0), Some(2), None]
+        );
+        assert_eq!(count, vec![1, 2, 2, 1, 1, 0]);
+        assert_eq!(sp.restore_path(4), Some(vec![3, 4]));
+        assert_eq!(dijkstra_paths(&graph, &[5]).restore_path(0), None);
+    }
+
+    #[test]
+    fn bellman_ford_works() {
+        //3->4->5->3が負の閉路で、頂点7には到達できない。
+        let edges = vec![
+            (0, 1, 4_i64),
+            (0, 2, 1),
+            (2, 1, -2),
+            (1, 3, 1),
+            (3, 4, -1),
+            (4, 5, -1),
+            (5, 3, -1),
+            (5, 6, 2),
+            (7, 0, 1),
+        ];
+        let graph = Csr::directed(8, &edges);
+        for bf in [bellman_ford_from(&graph, 0), spfa(&graph, 0)] {
+            assert_eq!(
+                bf.dist,
+                vec![
+                    Distance::Finite(0),
+                    Distance::Finite(-1),
+                    Distance::Finite(1),
+                    Distance::NegativeInfinity,
+                    Distance::NegativeInfinity,
+                    Distance::NegativeInfinity,
+                    Distance::NegativeInfinity,
+                    Distance::Unreachable,
+                ]
+            );
+            assert_eq!(bf.restore_path(1), Some(vec![0, 2, 1]));
+            assert_eq!(bf.restore_path(6), None);
+            assert_eq!(bf.restore_path(7), None);
+            let mut cycle = bf.negative_cycle.unwrap();
+            let p = cycle.iter().position(|&v| v == 3).unwrap();
+            cycle.rotate_left(p);
+            assert_eq!(cycle, vec![3, 4, 5]);
+        }
+
+        //始点から到達できない負の閉路は無視する。
+        let bf = bellman_ford_from(&graph, 6);
+        assert_eq!(bf.dist[6], Distance::Finite(0));
+        assert_eq!(bf.dist[0], Distance::Unreachable);
+        assert!(bf.negative_cycle.is_none());
+
+        let edges = vec![(0, 1, 2_i64), (0, 2, 5), (1, 2, -4), (2, 3, 1), (1, 3, 3)];
+        let graph = Csr::directed(4, &edges);
+        for bf in [bellman_ford_from(&graph, 0), spfa(&graph, 0)] {
+            assert_eq!(
+                bf.dist,
+                vec![
+                    Distance::Finite(0),
+                    Distance::Finite(2),
+                    Distance::Finite(-2),
+                    Distance::Finite(-1),
+                ]
+            );
+            assert_eq!(bf.restore_path(3), Some(vec![0, 1, 2, 3]));
+            assert!(bf.negative_cycle.is_none());
+        }
+    }
+
+    #[test]
+    fn warshall_floyd_works() {
+        let inf = 1_i64 << 60;
+        let mut graph = Matrix::new(5, 5, inf);
+        for (u, v, w) in [(0, 1, 5), (0, 2, 2), (2, 1, 1), (1, 3, 1), (4, 0, 7)] {
+            graph[u][v] = w;
+        }
+        let mut wf = WarshallFloyd::from_matrix(&graph, inf);
+        assert!(!wf.has_negative_cycle());
+        assert_eq!(wf.dist(0, 0), Distance::Finite(0));
+        assert_eq!(wf.dist(0, 1), Distance::Finite(3));
+        assert_eq!(wf.dist(4, 3), Distance::Finite(11));
+        assert_eq!(wf.dist(3, 0), Distance::Unreachable);
+        assert_eq!(wf.path(4, 3), Some(vec![4, 0, 2, 1, 3]));
+        assert_eq!(wf.path(2, 2), Some(vec![2]));
+        assert_eq!(wf.path(3, 0), None);
+
+        wf.add_edge(3, 0, -3);
+        assert_eq!(wf.dist(3, 1), Distance::Finite(0));
+        assert_eq!(wf.dist(1, 0), Distance::Finite(-2));
+        assert_eq!(wf.path(1, 2), Some(vec![1, 3, 0, 2]));
+        assert!(!wf.has_negative_cycle());
+
+        //1->2->1が負の閉路になり、そこを通る経路はすべて-infになる。
+        wf.add_edge(1, 2, -2);
+        assert_eq!(wf.negative_cycle_vertices(), vec![0, 1, 2, 3]);
+        assert_eq!(wf.dist(4, 3), Distance::NegativeInfinity);
+        assert_eq!(wf.dist(0, 4), Distance::Unreachable);
+        assert_eq!(wf.dist(4, 4), Distance::Finite(0));
+        assert_eq!(wf.path(4, 0), None);
+
+        let mut graph = vec![vec![None; 5]; 5];
+        for (u, v, w) in [(0, 1, 1), (1, 2, -2), (2, 1, 1), (2, 3, 1), (4, 0, 1)] {
+            graph[u][v] = Some(w);
+        }
+        let wf = WarshallFloyd::new(graph);
+        assert_eq!(wf.negative_cycle_vertices(), vec![1, 2]);
+        assert_eq!(wf.dist(4, 3), Distance::NegativeInfinity);
+        assert_eq!(wf.dist(0, 0), Distance::Finite(0));
+        assert_eq!(wf.dist(3, 3), Distance::Finite(0));
+        assert_eq!(wf.dist(3, 1), Distance::Unreachable);
+    }
+
+    #[test]
+    fn johnson_works() {
+        let edges = vec![(0, 1, 2_i64), (0, 2, 5), (1, 2, -4), (2, 3, 1), (3, 1, 4)];
+        let dist = johnson(&Csr::directed(5, &edges)).unwrap();
+        assert_eq!(dist[0], vec![Some(0), Some(2), Some(-2), Some(-1), None]);
+        assert_eq!(dist[2], vec![None, Some(5), Some(0), Some(1), None]);
+        assert_eq!(dist[4], vec![None, None, None, None, Some(0)]);
+
+        //負の閉路1->2->3->1は頂点0から到達できなくても検出する。
+        let edges = vec![(0, 4, 1_i64), (1, 2, -4), (2, 3, 1), (3, 1, 2)];
+        let mut cycle = johnson(&Csr::directed(5, &edges)).unwrap_err();
+        let p = cycle.iter().position(|&v| v == 1).unwrap();
+        cycle.rotate_left(p);
+        assert_eq!(cycle, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn warshall_floyd_negative_cycle_works() {
+        //全辺のコストが-1の完全グラフでは、緩和を続けると距離が指数的に小さくなる。
+        let n = 41;
+        let wf = WarshallFloyd::new(vec![vec![Some(-1_i64); n]; n]);
+        assert!(wf.has_negative_cycle());
+        assert_eq!(wf.negative_cycle_vertices().len(), n);
+        for i in 0..n {
+            for j in 0..n {
+                assert_eq!(wf.dist(i, j), Distance::NegativeInfinity);
+            }
+        }
+
+        let mut graph = vec![vec![None; n]; n];
+        for i in 0..n - 1 {
+            graph[i][i + 1] = Some(-50_000_000_i32);
+        }
+        let mut wf = WarshallFloyd::new(graph);
+        assert_eq!(wf.dist(0, n - 1), Distance::Finite(-2_000_000_000));
+        wf.add_edge(n - 1, 0, -1);
+        for i in 0..n {
+            for j in 0..n {
+                assert_eq!(wf.dist(i, j), Distance::NegativeInfinity);
+            }
+        }
+        wf.add_edge(0, 1, -1);
+        assert_eq!(wf.dist(1, 0), Distance::NegativeInfinity);
+
+        //大きな負の閉路がある状態で辺を追加しても、最初から構築した結果と一致する。
+        let n = 30;
+        let mut graph = vec![vec![None; n]; n];
+        for i in 0..n / 2 {
+            graph[i][(i + 1) % (n / 2)] = Some(-1_i64);
+        }
+        let mut wf = WarshallFloyd::new(graph.clone());
+        for i in 0..2 * n {
+            let (u, v, w) = ((i * 7 + 3) % n, (i * 11 + 5) % n, (i % 9) as i64 - 2);
+            wf.add_edge(u, v, w);
+            if !matches!(graph[u][v], Some(x) if x <= w) {
+                graph[u][v] = Some(w);
+            }
+            let rebuilt = WarshallFloyd::new(graph.clone());
+            for a in 0..n {
+                for b in 0..n {
+                    assert_eq!(wf.dist(a, b), rebuilt.dist(a, b));
+                }
+            }
+        }
+    }
+}
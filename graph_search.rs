@@ -1,4 +1,6 @@
-use std::collections::VecDeque;
+use crate::csr::Csr;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
 
 #[derive(PartialEq, Eq, Clone, Copy)]
 pub enum DfsType {
@@ -9,6 +11,10 @@ pub enum DfsType {
 
 #[allow(clippy::ptr_arg)]
 pub fn topological_sort(graph: &Vec<Vec<usize>>) -> Vec<usize> {
+    topological_sort_csr(&Csr::from_unweighted(graph))
+}
+
+pub fn topological_sort_csr<W>(graph: &Csr<W>) -> Vec<usize> {
     let mut r = vec![];
     let mut flag = vec![false; graph.len()];
     for i in 0..graph.len() {
@@ -21,8 +27,42 @@ pub fn topological_sort(graph: &Vec<Vec<usize>>) -> Vec<usize> {
     r
 }
 
+///トポロジカルソート(Kahn法)
+///
+///閉路が存在する場合は、閉路を構成する頂点列を辺の向きの順にErrで返す。
+pub fn topological_sort_kahn(graph: &[Vec<usize>]) -> Result<Vec<usize>, Vec<usize>> {
+    internal_kahn(graph, false)
+}
+
+///辞書順最小のトポロジカル順序を返す。
+///
+///閉路が存在する場合は、閉路を構成する頂点列を辺の向きの順にErrで返す。
+pub fn topological_sort_lex(graph: &[Vec<usize>]) -> Result<Vec<usize>, Vec<usize>> {
+    internal_kahn(graph, true)
+}
+
+///各頂点について、その頂点で終わる最長パスの辺数(層番号)を返す。
+///
+///層の数は最大値+1、DAGの最長パスの長さは最大値となる。
+///
+///閉路が存在する場合は、閉路を構成する頂点列をErrで返す。
+pub fn topological_layers(graph: &[Vec<usize>]) -> Result<Vec<usize>, Vec<usize>> {
+    let order = topological_sort_kahn(graph)?;
+    let mut layer = vec![0; graph.len()];
+    for &v in &order {
+        for &next_vertex in &graph[v] {
+            layer[next_vertex] = layer[next_vertex].max(layer[v] + 1);
+        }
+    }
+    Ok(layer)
+}
+
 #[allow(clippy::ptr_arg)]
 pub fn graph_dfs(start_vertex: usize, graph: &Vec<Vec<usize>>, t: DfsType) -> Vec<usize> {
+    graph_dfs_csr(start_vertex, &Csr::from_unweighted(graph), t)
+}
+
+pub fn graph_dfs_csr<W>(start_vertex: usize, graph: &Csr<W>, t: DfsType) -> Vec<usize> {
     let mut r = vec![];
     let mut flag = vec![false; graph.len()];
     flag[start_vertex] = true;
@@ -32,6 +72,10 @@ pub fn graph_dfs(start_vertex: usize, graph: &Vec<Vec<usize>>, t: DfsType) -> Ve
 
 #[allow(clippy::ptr_arg)]
 pub fn graph_bfs(start_vertex: usize, graph: &Vec<Vec<usize>>) -> Vec<usize> {
+    graph_bfs_csr(start_vertex, &Csr::from_unweighted(graph))
+}
+
+pub fn graph_bfs_csr<W>(start_vertex: usize, graph: &Csr<W>) -> Vec<usize> {
     let mut r = vec![];
     let mut flag = vec![false; graph.len()];
     let mut queue = VecDeque::new();
@@ -40,7 +84,7 @@ pub fn graph_bfs(start_vertex: usize, graph: &Vec<Vec<usize>>) -> Vec<usize> {
     while !queue.is_empty() {
         let pop = queue.pop_back().unwrap();
         r.push(pop);
-        for &next_vertex in &graph[pop] {
+        for next_vertex in graph.neighbors(pop) {
             if flag[next_vertex] {
                 continue;
             }
@@ -51,18 +95,217 @@ pub fn graph_bfs(start_vertex: usize, graph: &Vec<Vec<usize>>) -> Vec<usize> {
     r
 }
 
-#[allow(clippy::ptr_arg)]
-fn internal_graph_dfs(
+///BFSの結果(距離と最短路木の親)
+pub struct BfsResult {
+    pub dist: Vec<Option<usize>>,
+    pub parent: Vec<Option<usize>>,
+}
+
+impl BfsResult {
+    ///始点からtまでの最短路の頂点列を返す。到達できない場合はNoneを返す。
+    pub fn restore_path(&self, t: usize) -> Option<Vec<usize>> {
+        self.dist[t]?;
+        let mut r = vec![t];
+        let mut v = t;
+        while let Some(p) = self.parent[v] {
+            r.push(p);
+            v = p;
+        }
+        r.reverse();
+        Some(r)
+    }
+}
+
+///幅優先探索で各頂点への距離を求める。
+///
+///starts:始点の集合(複数指定すると最も近い始点からの距離になる)
+pub fn bfs_dist(starts: &[usize], graph: &[Vec<usize>]) -> BfsResult {
+    let mut dist = vec![None; graph.len()];
+    let mut parent = vec![None; graph.len()];
+    let mut queue = VecDeque::new();
+    for &s in starts {
+        if dist[s].is_none() {
+            dist[s] = Some(0);
+            queue.push_back(s);
+        }
+    }
+    while let Some(v) = queue.pop_front() {
+        let d = dist[v].unwrap();
+        for &next_vertex in &graph[v] {
+            if dist[next_vertex].is_some() {
+                continue;
+            }
+            dist[next_vertex] = Some(d + 1);
+            parent[next_vertex] = Some(v);
+            queue.push_back(next_vertex);
+        }
+    }
+    BfsResult { dist, parent }
+}
+
+///01-BFS
+///
+///graph:グラフ情報(隣接リスト、dijkstraと同じ形式で、コストは0または1)
+///
+///starts:始点の集合
+pub fn bfs_01(starts: &[usize], graph: &[Vec<(usize, isize)>]) -> BfsResult {
+    let mut dist: Vec<Option<usize>> = vec![None; graph.len()];
+    let mut parent = vec![None; graph.len()];
+    let mut queue = VecDeque::new();
+    for &s in starts {
+        dist[s] = Some(0);
+        queue.push_back((0, s));
+    }
+    while let Some((d, v)) = queue.pop_front() {
+        if dist[v] != Some(d) {
+            continue;
+        }
+        for &(next_vertex, cost) in &graph[v] {
+            assert!(cost == 0 || cost == 1);
+            let nd = d + cost as usize;
+            if !matches!(dist[next_vertex], Some(x) if x <= nd) {
+                dist[next_vertex] = Some(nd);
+                parent[next_vertex] = Some(v);
+                if cost == 0 {
+                    queue.push_front((nd, next_vertex));
+                } else {
+                    queue.push_back((nd, next_vertex));
+                }
+            }
+        }
+    }
+    BfsResult { dist, parent }
+}
+
+///二部グラフ判定の結果
+pub struct Bipartite {
+    ///各頂点の色(0または1)
+    pub color: Vec<usize>,
+    ///各頂点の連結成分番号
+    pub component: Vec<usize>,
+    ///連結成分ごとの(色0の頂点数, 色1の頂点数)
+    pub part_sizes: Vec<(usize, usize)>,
+}
+
+///二部グラフ判定(無向グラフ)
+///
+///二部グラフなら連結成分ごとの2彩色を返す。そうでなければ奇閉路の頂点列をErrで返す。
+pub fn bipartite(graph: &[Vec<usize>]) -> Result<Bipartite, Vec<usize>> {
+    let n = graph.len();
+    let mut color = vec![usize::MAX; n];
+    let mut component = vec![0; n];
+    let mut parent = vec![usize::MAX; n];
+    let mut part_sizes = vec![];
+    let mut queue = VecDeque::new();
+    for s in 0..n {
+        if color[s] != usize::MAX {
+            continue;
+        }
+        let id = part_sizes.len();
+        let mut sizes = (0, 0);
+        color[s] = 0;
+        queue.push_back(s);
+        while let Some(v) = queue.pop_front() {
+            component[v] = id;
+            if color[v] == 0 {
+                sizes.0 += 1;
+            } else {
+                sizes.1 += 1;
+            }
+            for &next_vertex in &graph[v] {
+                if color[next_vertex] == usize::MAX {
+                    color[next_vertex] = color[v] ^ 1;
+                    parent[next_vertex] = v;
+                    queue.push_back(next_vertex);
+                } else if color[next_vertex] == color[v] {
+                    //BFS木上で2頂点の共通祖先まで遡ると奇閉路になる。
+                    let mut a = vec![v];
+                    let mut b = vec![next_vertex];
+                    let (mut x, mut y) = (v, next_vertex);
+                    while x != y {
+                        x = parent[x];
+                        y = parent[y];
+                        a.push(x);
+                        b.push(y);
+                    }
+                    b.pop();
+                    let mut cycle = a;
+                    cycle.reverse();
+                    cycle.extend(b);
+                    return Err(cycle);
+                }
+            }
+        }
+        part_sizes.push(sizes);
+    }
+    Ok(Bipartite {
+        color,
+        component,
+        part_sizes,
+    })
+}
+
+///オイラー路(Hierholzer法)
+///
+///n:頂点数
+///
+///edges:辺リスト(添字を辺番号とする。多重辺・自己ループ可)
+///
+///directed:有向グラフならtrue
+///
+///オイラー路が存在すれば(辺番号列, 頂点列)を返す。存在しなければNoneを返す。
+pub fn eulerian_trail(
+    n: usize,
+    edges: &[(usize, usize)],
+    directed: bool,
+) -> Option<(Vec<usize>, Vec<usize>)> {
+    let balance = internal_euler_balance(n, edges, directed);
+    let start = if directed {
+        let starts = (0..n).filter(|&i| balance[i] == 1).collect::<Vec<_>>();
+        let ends = (0..n).filter(|&i| balance[i] == -1).count();
+        if (0..n).any(|i| balance[i].abs() > 1) || starts.len() > 1 || starts.len() != ends {
+            return None;
+        }
+        starts.first().copied()
+    } else {
+        let odds = (0..n).filter(|&i| balance[i] % 2 == 1).collect::<Vec<_>>();
+        if odds.len() > 2 {
+            return None;
+        }
+        odds.first().copied()
+    };
+    let start = start.or_else(|| edges.first().map(|e| e.0));
+    internal_hierholzer(n, edges, directed, start)
+}
+
+///オイラー閉路(Hierholzer法)
+///
+///オイラー閉路が存在すれば(辺番号列, 頂点列)を返す。頂点列の最初と最後は同じ頂点になる。
+pub fn eulerian_circuit(
+    n: usize,
+    edges: &[(usize, usize)],
+    directed: bool,
+) -> Option<(Vec<usize>, Vec<usize>)> {
+    let balance = internal_euler_balance(n, edges, directed);
+    if directed && balance.iter().any(|&b| b != 0)
+        || !directed && balance.iter().any(|&b| b % 2 != 0)
+    {
+        return None;
+    }
+    internal_hierholzer(n, edges, directed, edges.first().map(|e| e.0))
+}
+
+fn internal_graph_dfs<W>(
     vertex: usize,
-    flag: &mut Vec<bool>,
-    graph: &Vec<Vec<usize>>,
+    flag: &mut [bool],
+    graph: &Csr<W>,
     r: &mut Vec<usize>,
     t: DfsType,
 ) {
     if t == DfsType::Preorder {
         r.push(vertex);
     }
-    for (idx, &next_vertex) in graph[vertex].iter().enumerate() {
+    for (idx, next_vertex) in graph.neighbors(vertex).enumerate() {
         if flag[next_vertex] {
             continue;
         }
@@ -76,3 +319,291 @@ fn internal_graph_dfs(
         r.push(vertex);
     }
 }
+
+fn internal_kahn(graph: &[Vec<usize>], lex: bool) -> Result<Vec<usize>, Vec<usize>> {
+    let n = graph.len();
+    let mut indeg = vec![0; n];
+    for v in graph {
+        for &next_vertex in v {
+            indeg[next_vertex] += 1;
+        }
+    }
+
+    let mut r = Vec::with_capacity(n);
+    if lex {
+        let mut heap = (0..n)
+            .filter(|&i| indeg[i] == 0)
+            .map(Reverse)
+            .collect::<BinaryHeap<_>>();
+        while let Some(Reverse(v)) = heap.pop() {
+            r.push(v);
+            for &next_vertex in &graph[v] {
+                indeg[next_vertex] -= 1;
+                if indeg[next_vertex] == 0 {
+                    heap.push(Reverse(next_vertex));
+                }
+            }
+        }
+    } else {
+        let mut queue = (0..n).filter(|&i| indeg[i] == 0).collect::<VecDeque<_>>();
+        while let Some(v) = queue.pop_front() {
+            r.push(v);
+            for &next_vertex in &graph[v] {
+                indeg[next_vertex] -= 1;
+                if indeg[next_vertex] == 0 {
+                    queue.push_back(next_vertex);
+                }
+            }
+        }
+    }
+
+    if r.len() == n {
+        return Ok(r);
+    }
+
+    //残った頂点はすべて残った頂点からの入辺を持つので、入辺を逆に辿れば閉路に当たる。
+    let mut pred = vec![n; n];
+    for (from, v) in graph.iter().enumerate() {
+        if indeg[from] == 0 {
+            continue;
+        }
+        for &to in v {
+            if indeg[to] > 0 {
+                pred[to] = from;
+            }
+        }
+    }
+    let mut pos = vec![n; n];
+    let mut walk = vec![];
+    let mut v = (0..n).find(|&i| indeg[i] > 0).unwrap();
+    while pos[v] == n {
+        pos[v] = walk.len();
+        walk.push(v);
+        v = pred[v];
+    }
+    let mut cycle = walk.split_off(pos[v]);
+    cycle.reverse();
+    Err(cycle)
+}
+
+//有向なら出次数-入次数、無向なら次数
+fn internal_euler_balance(n: usize, edges: &[(usize, usize)], directed: bool) -> Vec<isize> {
+    let mut balance = vec![0_isize; n];
+    for &(from, to) in edges {
+        balance[from] += 1;
+        if directed {
+            balance[to] -= 1;
+        } else {
+            balance[to] += 1;
+        }
+    }
+    balance
+}
+
+fn internal_hierholzer(
+    n: usize,
+    edges: &[(usize, usize)],
+    directed: bool,
+    start: Option<usize>,
+) -> Option<(Vec<usize>, Vec<usize>)> {
+    let start = match start {
+        Some(s) => s,
+        None => return Some((vec![], if n > 0 { vec![0] } else { vec![] })),
+    };
+    let mut graph = vec![vec![]; n];
+    for (id, &(from, to)) in edges.iter().enumerate() {
+        graph[from].push((to, id));
+        if !directed {
+            graph[to].push((from, id));
+        }
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut ptr = vec![0; n];
+    let mut edge_path = Vec::with_capacity(edges.len());
+    let mut vertex_path = Vec::with_capacity(edges.len() + 1);
+    let mut stack = vec![(start, usize::MAX)];
+    while let Some(&(v, e)) = stack.last() {
+        while ptr[v] < graph[v].len() && used[graph[v][ptr[v]].1] {
+            ptr[v] += 1;
+        }
+        if ptr[v] < graph[v].len() {
+            let (to, id) = graph[v][ptr[v]];
+            ptr[v] += 1;
+            used[id] = true;
+            stack.push((to, id));
+        } else {
+            stack.pop();
+            vertex_path.push(v);
+            if e != usize::MAX {
+                edge_path.push(e);
+            }
+        }
+    }
+
+    //全ての辺を使えなかった場合は非連結
+    if edge_path.len() != edges.len() {
+        return None;
+    }
+    edge_path.reverse();
+    vertex_path.reverse();
+    Some((edge_path, vertex_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topological_sort_kahn_works() {
+        let graph = vec![vec![2], vec![3, 2], vec![4], vec![4], vec![], vec![]];
+        let order = topological_sort_kahn(&graph).unwrap();
+        let mut pos = vec![0; graph.len()];
+        for (i, &v) in order.iter().enumerate() {
+            pos[v] = i;
+        }
+        for (v, adj) in graph.iter().enumerate() {
+            assert!(adj.iter().all(|&to| pos[v] < pos[to]));
+        }
+        assert_eq!(topological_sort_lex(&graph), Ok(vec![0, 1, 2, 3, 4, 5]));
+        assert_eq!(topological_layers(&graph), Ok(vec![0, 0, 1, 1, 2, 0]));
+
+        //3->0->1->2->0の閉路がある。
+        let graph = vec![vec![1], vec![2], vec![0], vec![0]];
+        for r in [
+            topological_sort_kahn(&graph),
+            topological_sort_lex(&graph),
+            topological_layers(&graph),
+        ] {
+            let mut cycle = r.unwrap_err();
+            let p = cycle.iter().position(|&v| v == 0).unwrap();
+            cycle.rotate_left(p);
+            assert_eq!(cycle, vec![0, 1, 2]);
+        }
+        assert_eq!(topological_sort_kahn(&[vec![0]]), Err(vec![0]));
+    }
+
+    //辺番号列と頂点列がすべての辺をちょうど1回ずつ通る路になっているか
+    fn is_eulerian_trail(
+        edges: &[(usize, usize)],
+        directed: bool,
+        (ids, vertices): &(Vec<usize>, Vec<usize>),
+    ) -> bool {
+        let mut used = vec![false; edges.len()];
+        ids.len() == edges.len()
+            && vertices.len() == ids.len() + 1
+            && ids.iter().enumerate().all(|(i, &id)| {
+                let (a, b) = (vertices[i], vertices[i + 1]);
+                let ok = edges[id] == (a, b) || !directed && edges[id] == (b, a);
+                ok && !std::mem::replace(&mut used[id], true)
+            })
+    }
+
+    #[test]
+    fn eulerian_trail_works() {
+        let edges = vec![(0, 1), (1, 2), (2, 0), (0, 3)];
+        let trail = eulerian_trail(4, &edges, true).unwrap();
+        assert!(is_eulerian_trail(&edges, true, &trail));
+        assert_eq!((trail.1[0], trail.1[4]), (0, 3));
+        assert_eq!(eulerian_circuit(4, &edges, true), None);
+
+        let edges = vec![(0, 1), (1, 2), (2, 0), (2, 3)];
+        let trail = eulerian_trail(4, &edges, false).unwrap();
+        assert!(is_eulerian_trail(&edges, false, &trail));
+        assert_eq!(eulerian_circuit(4, &edges, false), None);
+
+        //自己ループと多重辺を含む
+        let edges = vec![(0, 1), (1, 2), (2, 0), (0, 0), (0, 1), (1, 0)];
+        for directed in [true, false] {
+            let circuit = eulerian_circuit(3, &edges, directed).unwrap();
+            assert!(is_eulerian_trail(&edges, directed, &circuit));
+            assert_eq!(circuit.1.first(), circuit.1.last());
+        }
+
+        //次数の条件は満たすが連結でない
+        let edges = vec![(0, 1), (1, 0), (2, 3), (3, 2)];
+        assert_eq!(eulerian_circuit(4, &edges, true), None);
+        assert_eq!(eulerian_trail(4, &edges, false), None);
+        assert_eq!(eulerian_trail(3, &[(0, 1), (0, 2)], true), None);
+        assert_eq!(eulerian_trail(4, &[(0, 1), (0, 2), (0, 3)], false), None);
+        assert_eq!(eulerian_circuit(3, &[], true), Some((vec![], vec![0])));
+    }
+
+    #[test]
+    fn bfs_dist_works() {
+        let graph = vec![
+            vec![1, 4],
+            vec![0, 2],
+            vec![1, 3],
+            vec![2, 4],
+            vec![0, 3],
+            vec![],
+        ];
+        let r = bfs_dist(&[0], &graph);
+        assert_eq!(
+            r.dist,
+            vec![Some(0), Some(1), Some(2), Some(2), Some(1), None]
+        );
+        assert_eq!(r.restore_path(0), Some(vec![0]));
+        assert_eq!(r.restore_path(3), Some(vec![0, 4, 3]));
+        assert_eq!(r.restore_path(5), None);
+
+        let r = bfs_dist(&[0, 2, 0], &graph);
+        assert_eq!(
+            r.dist,
+            vec![Some(0), Some(1), Some(0), Some(1), Some(1), None]
+        );
+        assert_eq!(r.restore_path(3), Some(vec![2, 3]));
+
+        let graph = vec![
+            vec![(1, 1), (2, 0)],
+            vec![(3, 0)],
+            vec![(1, 0), (3, 1)],
+            vec![(4, 1)],
+            vec![],
+        ];
+        let r = bfs_01(&[0], &graph);
+        assert_eq!(r.dist, vec![Some(0), Some(0), Some(0), Some(0), Some(1)]);
+        assert_eq!(r.restore_path(4), Some(vec![0, 2, 1, 3, 4]));
+        let r = bfs_01(&[3, 1], &graph);
+        assert_eq!(r.dist, vec![None, Some(0), None, Some(0), Some(1)]);
+        assert_eq!(r.restore_path(0), None);
+        assert_eq!(r.restore_path(4), Some(vec![3, 4]));
+    }
+
+    #[test]
+    fn bipartite_works() {
+        let graph = vec![
+            vec![1, 3],
+            vec![0, 2],
+            vec![1, 3],
+            vec![2, 0],
+            vec![5],
+            vec![4],
+            vec![],
+        ];
+        let b = bipartite(&graph).unwrap();
+        assert_eq!(b.color, vec![0, 1, 0, 1, 0, 1, 0]);
+        assert_eq!(b.component, vec![0, 0, 0, 0, 1, 1, 2]);
+        assert_eq!(b.part_sizes, vec![(2, 2), (1, 1), (1, 0)]);
+
+        //5頂点の閉路に葉を1つつけたグラフ
+        let graph = vec![
+            vec![5, 1, 4],
+            vec![0, 2],
+            vec![1, 3],
+            vec![2, 4],
+            vec![3, 0],
+            vec![0],
+        ];
+        let cycle = bipartite(&graph).err().unwrap();
+        assert_eq!(cycle.len(), 5);
+        for i in 0..cycle.len() {
+            let (a, b) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+            assert!(graph[a].contains(&b));
+        }
+        let mut sorted = cycle.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, vec![0, 1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::VecDeque;
+
+pub const DIR4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+pub const DIR8: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+///(y,x)の近傍のうち、h*wのグリッドからはみ出さないマスを返すイテレータ
+///
+///dirs:DIR4またはDIR8
+pub fn neighbors(
+    y: usize,
+    x: usize,
+    h: usize,
+    w: usize,
+    dirs: &'static [(isize, isize)],
+) -> impl Iterator<Item = (usize, usize)> {
+    dirs.iter().filter_map(move |&(dy, dx)| {
+        let ny = y.wrapping_add(dy as usize);
+        let nx = x.wrapping_add(dx as usize);
+        if ny < h && nx < w {
+            Some((ny, nx))
+        } else {
+            None
+        }
+    })
+}
+
+///グリッド上の幅優先探索
+///
+///grid:グリッド(Vec<Vec<T>>またはMatrix<T>)
+///
+///starts:始点の集合
+///
+///passable:通行可能なマスならtrueを返す関数
+///
+///各マスへの距離を返す。(到達できない場合はNone)
+pub fn grid_bfs<T, F>(
+    grid: &[Vec<T>],
+    starts: &[(usize, usize)],
+    dirs: &'static [(isize, isize)],
+    passable: F,
+) -> Vec<Vec<Option<usize>>>
+where
+    F: Fn(&T) -> bool,
+{
+    let h = grid.len();
+    let w = if h > 0 { grid[0].len() } else { 0 };
+    let mut dist = vec![vec![None; w]; h];
+    let mut queue = VecDeque::new();
+    for &(y, x) in starts {
+        if passable(&grid[y][x]) && dist[y][x].is_none() {
+            dist[y][x] = Some(0);
+            queue.push_back((y, x));
+        }
+    }
+    while let Some((y, x)) = queue.pop_front() {
+        let d = dist[y][x].unwrap();
+        for (ny, nx) in neighbors(y, x, h, w, dirs) {
+            if dist[ny][nx].is_some() || !passable(&grid[ny][nx]) {
+                continue;
+            }
+            dist[ny][nx] = Some(d + 1);
+            queue.push_back((ny, nx));
+        }
+    }
+    dist
+}
+
+///(y,x)から到達できる通行可能なマスを列挙する。(塗りつぶし)
+pub fn flood_fill<T, F>(
+    grid: &[Vec<T>],
+    y: usize,
+    x: usize,
+    dirs: &'static [(isize, isize)],
+    passable: F,
+) -> Vec<(usize, usize)>
+where
+    F: Fn(&T) -> bool,
+{
+    let dist = grid_bfs(grid, &[(y, x)], dirs, passable);
+    let mut r = vec![];
+    for (i, v) in dist.iter().enumerate() {
+        for (j, d) in v.iter().enumerate() {
+            if d.is_some() {
+                r.push((i, j));
+            }
+        }
+    }
+    r
+}
+
+///通行可能なマスの連結成分にラベルを付ける。
+///
+///(成分数, 各マスの成分番号(通行不可能なマスはNone))を返す。
+pub fn label_components<T, F>(
+    grid: &[Vec<T>],
+    dirs: &'static [(isize, isize)],
+    passable: F,
+) -> (usize, Vec<Vec<Option<usize>>>)
+where
+    F: Fn(&T) -> bool,
+{
+    let h = grid.len();
+    let w = if h > 0 { grid[0].len() } else { 0 };
+    let mut label = vec![vec![None; w]; h];
+    let mut count = 0;
+    let mut stack = vec![];
+    for sy in 0..h {
+        for sx in 0..w {
+            if label[sy][sx].is_some() || !passable(&grid[sy][sx]) {
+                continue;
+            }
+            label[sy][sx] = Some(count);
+            stack.push((sy, sx));
+            while let Some((y, x)) = stack.pop() {
+                for (ny, nx) in neighbors(y, x, h, w, dirs) {
+                    if label[ny][nx].is_some() || !passable(&grid[ny][nx]) {
+                        continue;
+                    }
+                    label[ny][nx] = Some(count);
+                    stack.push((ny, nx));
+                }
+            }
+            count += 1;
+        }
+    }
+    (count, label)
+}
+
+///グリッドを隣接リストに変換する。マス(y,x)は頂点y*w+xになる。
+///
+///通行不可能なマスは辺を持たない頂点になる。
+pub fn grid_to_graph<T, F>(
+    grid: &[Vec<T>],
+    dirs: &'static [(isize, isize)],
+    passable: F,
+) -> Vec<Vec<usize>>
+where
+    F: Fn(&T) -> bool,
+{
+    let h = grid.len();
+    let w = if h > 0 { grid[0].len() } else { 0 };
+    let mut graph = vec![vec![]; h * w];
+    for y in 0..h {
+        for x in 0..w {
+            if !passable(&grid[y][x]) {
+                continue;
+            }
+            for (ny, nx) in neighbors(y, x, h, w, dirs) {
+                if passable(&grid[ny][nx]) {
+                    graph[y * w + x].push(ny * w + nx);
+                }
+            }
+        }
+    }
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::Matrix;
+
+    #[test]
+    fn grid_works() {
+        let grid = vec![
+            b"..#.".to_vec(),
+            b".##.".to_vec(),
+            b"...#".to_vec(),
+            b"##.#".to_vec(),
+        ];
+        let dist = grid_bfs(&grid, &[(0, 0)], &DIR4, |&c| c == b'.');
+        assert_eq!(dist[3][2], Some(5));
+        assert_eq!(dist[0][3], None);
+
+        let (count, label) = label_components(&grid, &DIR4, |&c| c == b'.');
+        assert_eq!(count, 2);
+        assert_eq!(label[0][3], label[1][3]);
+        assert_eq!(label[0][2], None);
+        let (count, _) = label_components(&grid, &DIR8, |&c| c == b'.');
+        assert_eq!(count, 1);
+        let (count, _) = label_components(&grid, &DIR8, |&c| c == b'#');
+        assert_eq!(count, 2);
+
+        assert_eq!(flood_fill(&grid, 0, 3, &DIR4, |&c| c == b'.').len(), 2);
+
+        let graph = grid_to_graph(&grid, &DIR4, |&c| c == b'.');
+        assert_eq!(graph.len(), 16);
+        assert_eq!(graph[0], vec![1, 4]);
+        assert!(graph[2].is_empty());
+
+        let m = Matrix::from(vec![vec![0, 1], vec![0, 0]]);
+        let dist = grid_bfs(&m, &[(0, 0)], &DIR4, |&c| c == 0);
+        assert_eq!(dist[1][1], Some(2));
+        assert_eq!(neighbors(0, 0, 2, 2, &DIR8).count(), 3);
+    }
+}
@@ -0,0 +1,131 @@
+use crate::matrix::Matrix;
+
+///割当問題(ハンガリアン法、O(h^2 w))
+///
+///cost:h*wのコスト行列(長方形でもよい)
+///
+///(コストの総和の最小値, 各行に割り当てた列)を返す。h>wの場合はw行だけが割り当てられ、残りの行はNoneになる。
+pub fn hungarian(cost: &Matrix<i64>) -> (i64, Vec<Option<usize>>) {
+    let h = cost.len();
+    if h == 0 {
+        return (0, vec![]);
+    }
+    let w = cost.w();
+    if h <= w {
+        let assign = internal_hungarian(h, w, |i, j| cost[i][j]);
+        let total = (0..h).map(|i| cost[i][assign[i]]).sum();
+        (total, assign.into_iter().map(Some).collect())
+    } else {
+        //転置して列を行に割り当てる。
+        let assign = internal_hungarian(w, h, |i, j| cost[j][i]);
+        let mut r = vec![None; h];
+        let mut total = 0;
+        for (j, &i) in assign.iter().enumerate() {
+            r[i] = Some(j);
+            total += cost[i][j];
+        }
+        (total, r)
+    }
+}
+
+///割当問題(重みの総和の最大化)
+pub fn hungarian_max(weight: &Matrix<i64>) -> (i64, Vec<Option<usize>>) {
+    let negated = Matrix::from(
+        weight
+            .iter()
+            .map(|v| v.iter().map(|&x| -x).collect())
+            .collect(),
+    );
+    let (total, assign) = hungarian(&negated);
+    (-total, assign)
+}
+
+//n<=mを仮定して、各行に割り当てた列を返す。
+fn internal_hungarian<F>(n: usize, m: usize, a: F) -> Vec<usize>
+where
+    F: Fn(usize, usize) -> i64,
+{
+    const INF: i64 = i64::MAX;
+    //ポテンシャルと、各列に割り当てた行(1-indexed、0は未割当)
+    let mut u = vec![0; n + 1];
+    let mut v = vec![0; m + 1];
+    let mut p = vec![0; m + 1];
+    let mut way = vec![0; m + 1];
+    for i in 1..=n {
+        p[0] = i;
+        let mut j0 = 0;
+        let mut minv = vec![INF; m + 1];
+        let mut used = vec![false; m + 1];
+        loop {
+            used[j0] = true;
+            let i0 = p[j0];
+            let mut delta = INF;
+            let mut j1 = 0;
+            for j in 1..=m {
+                if used[j] {
+                    continue;
+                }
+                let cur = a(i0 - 1, j - 1) - u[i0] - v[j];
+                if cur < minv[j] {
+                    minv[j] = cur;
+                    way[j] = j0;
+                }
+                if minv[j] < delta {
+                    delta = minv[j];
+                    j1 = j;
+                }
+            }
+            for j in 0..=m {
+                if used[j] {
+                    u[p[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    minv[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if p[j0] == 0 {
+                break;
+            }
+        }
+        loop {
+            let j1 = way[j0];
+            p[j0] = p[j1];
+            j0 = j1;
+            if j0 == 0 {
+                break;
+            }
+        }
+    }
+    let mut r = vec![0; n];
+    for j in 1..=m {
+        if p[j] != 0 {
+            r[p[j] - 1] = j - 1;
+        }
+    }
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hungarian_works() {
+        let cost = Matrix::from(vec![vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]]);
+        let (total, assign) = hungarian(&cost);
+        assert_eq!(total, 5);
+        assert_eq!(assign, vec![Some(1), Some(0), Some(2)]);
+
+        let (total, _) = hungarian_max(&cost);
+        assert_eq!(total, 11);
+
+        let cost = Matrix::from(vec![vec![7, 1], vec![3, 9], vec![2, 8]]);
+        let (total, assign) = hungarian(&cost);
+        assert_eq!(total, 3);
+        assert_eq!(assign, vec![Some(1), None, Some(0)]);
+        let (total, assign) = hungarian(&cost.t());
+        assert_eq!(total, 3);
+        assert_eq!(assign, vec![Some(2), Some(0)]);
+    }
+}
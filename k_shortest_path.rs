@@ -0,0 +1,460 @@
+use crate::csr::Csr;
+use crate::graph::{dijkstra_count, dijkstra_paths, Cost};
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, VecDeque};
+use std::ops::Sub;
+
+///経路(コスト, 頂点列, 辺番号の列)
+#[derive(Clone, Debug, PartialEq)]
+pub struct KPath<T> {
+    pub cost: T,
+    pub vertices: Vec<usize>,
+    pub edges: Vec<usize>,
+}
+
+///sからtへの単純パスをコストの小さい順にk個求める(Yenのアルゴリズム、O(kn(m + n log n)))
+///
+///graph:グラフ情報(コストは非負)
+pub fn yen_k_shortest_paths<T>(graph: &Csr<T>, s: usize, t: usize, k: usize) -> Vec<KPath<T>>
+where
+    T: Cost,
+{
+    let n = graph.len();
+    let mut banned_vertex = vec![false; n];
+    let mut banned_edge = vec![false; internal_edge_id_bound(graph)];
+    //確定した経路と各頂点までのコスト
+    let mut found: Vec<(KPath<T>, Vec<T>)> = vec![];
+    if k == 0 {
+        return vec![];
+    }
+    match internal_restricted_dijkstra(graph, s, t, &banned_vertex, &banned_edge) {
+        Some(first) => found.push(first),
+        None => return vec![],
+    }
+    let mut seen = BTreeSet::new();
+    seen.insert(found[0].0.edges.clone());
+    let mut candidates = BinaryHeap::new();
+    while found.len() < k {
+        let (last, last_prefix) = found.last().unwrap();
+        for i in 0..last.edges.len() {
+            //i番目の頂点までを固定し、そこから先を別の経路にする。
+            let spur = last.vertices[i];
+            let root = &last.edges[..i];
+            for (p, _) in &found {
+                if p.edges.len() > i && &p.edges[..i] == root {
+                    banned_edge[p.edges[i]] = true;
+                }
+            }
+            for &v in &last.vertices[..i] {
+                banned_vertex[v] = true;
+            }
+            if let Some((path, prefix)) =
+                internal_restricted_dijkstra(graph, spur, t, &banned_vertex, &banned_edge)
+            {
+                let mut edges = root.to_vec();
+                edges.extend_from_slice(&path.edges);
+                if seen.insert(edges.clone()) {
+                    let mut vertices = last.vertices[..i].to_vec();
+                    vertices.extend_from_slice(&path.vertices);
+                    let mut new_prefix = last_prefix[..i].to_vec();
+                    new_prefix.extend(prefix.iter().map(|&c| last_prefix[i].plus(c)));
+                    let cost = *new_prefix.last().unwrap();
+                    candidates.push(Reverse((cost, edges, vertices, new_prefix)));
+                }
+            }
+            for (p, _) in &found {
+                if p.edges.len() > i {
+                    banned_edge[p.edges[i]] = false;
+                }
+            }
+            for &v in &last.vertices[..i] {
+                banned_vertex[v] = false;
+            }
+        }
+        match candidates.pop() {
+            Some(Reverse((cost, edges, vertices, prefix))) => found.push((
+                KPath {
+                    cost,
+                    vertices,
+                    edges,
+                },
+                prefix,
+            )),
+            None => break,
+        }
+    }
+    found.into_iter().map(|(p, _)| p).collect()
+}
+
+///sからtへのウォーク(同じ頂点や辺を何度通ってもよい経路)のコストを小さい順にk個求める(Eppsteinのアルゴリズム、O(m log m + k log k))
+///
+///graph:グラフ情報(コストは非負)
+pub fn k_shortest_walks<T>(graph: &Csr<T>, s: usize, t: usize, k: usize) -> Vec<T>
+where
+    T: Cost + Sub<Output = T>,
+{
+    const NIL: usize = usize::MAX;
+    let n = graph.len();
+    let reversed = internal_reverse(graph);
+    //tを根とする最短路木(nextはtに向かって次の頂点)
+    let sp = dijkstra_paths(&reversed, &[t]);
+    let (dist, next) = (sp.dist, sp.prev);
+    if k == 0 || dist[s].is_none() {
+        return vec![];
+    }
+    let mut tree_edge = vec![None; n];
+    let mut children = vec![vec![]; n];
+    for u in 0..n {
+        if let (Some(p), Some(du)) = (next[u], dist[u]) {
+            tree_edge[u] = graph
+                .adj(u)
+                .iter()
+                .position(|&(to, w)| to == p && dist[p].map(|dp| dp.plus(w)) == Some(du));
+            children[p].push(u);
+        }
+    }
+
+    //各頂点から最短路木を辿ったときに使える脇道の辺を、永続ヒープで持つ。
+    let mut heap = PersistentHeap::new();
+    let mut root = vec![NIL; n];
+    let mut queue = VecDeque::new();
+    queue.push_back(t);
+    while let Some(u) = queue.pop_front() {
+        let mut h = next[u].map_or(NIL, |p| root[p]);
+        let du = dist[u].unwrap();
+        for (j, &(to, w)) in graph.adj(u).iter().enumerate() {
+            if tree_edge[u] == Some(j) {
+                continue;
+            }
+            if let Some(dt) = dist[to] {
+                h = heap.insert(h, dt.plus(w) - du, to);
+            }
+        }
+        root[u] = h;
+        queue.extend(children[u].iter().copied());
+    }
+
+    let mut r = vec![dist[s].unwrap()];
+    let mut pq = BinaryHeap::new();
+    if root[s] != NIL {
+        pq.push(Reverse((r[0].plus(heap.key(root[s])), root[s])));
+    }
+    while r.len() < k {
+        let (cost, x) = match pq.pop() {
+            Some(Reverse(e)) => e,
+            None => break,
+        };
+        r.push(cost);
+        let node = &heap.nodes[x];
+        for &c in &[node.left, node.right] {
+            if c != NIL {
+                pq.push(Reverse(((cost - node.key).plus(heap.key(c)), c)));
+            }
+        }
+        let h = root[node.to];
+        if h != NIL {
+            pq.push(Reverse((cost.plus(heap.key(h)), h)));
+        }
+    }
+    r
+}
+
+///最短経路DAGを求める。
+///
+///dist:始点からの最短距離(dijkstra_pathsの結果)
+///
+///最短経路に使われうる辺を、各頂点から(行き先, 辺番号)のリストとして返す。(コストが0の辺があると閉路ができることがある)
+pub fn shortest_path_dag<T>(graph: &Csr<T>, dist: &[Option<T>]) -> Vec<Vec<(usize, usize)>>
+where
+    T: Cost,
+{
+    (0..graph.len())
+        .map(|v| match dist[v] {
+            Some(d) => graph
+                .adj(v)
+                .iter()
+                .zip(graph.edge_ids(v))
+                .filter(|&(&(to, w), _)| dist[to] == Some(d.plus(w)))
+                .map(|(&(to, _), &id)| (to, id))
+                .collect(),
+            None => vec![],
+        })
+        .collect()
+}
+
+///s-t最短経路に使われる頂点・辺
+pub struct ShortestPathUsage {
+    ///いずれかの最短経路が通る頂点
+    pub vertex_some: Vec<bool>,
+    ///すべての最短経路が通る頂点
+    pub vertex_every: Vec<bool>,
+    ///いずれかの最短経路が通る辺(辺番号で引く)
+    pub edge_some: Vec<bool>,
+    ///すべての最短経路が通る辺(辺番号で引く)
+    pub edge_every: Vec<bool>,
+}
+
+///s-t最短経路に使われる頂点と辺を求める。tに到達できなければNoneを返す。
+///
+///最短経路の本数を2つの素数で割った余りで数えて判定するので、ごく低い確率で誤判定する。コストは正である必要がある。
+pub fn shortest_path_usage<T>(graph: &Csr<T>, s: usize, t: usize) -> Option<ShortestPathUsage>
+where
+    T: Cost,
+{
+    const MODS: [u64; 2] = [998_244_353, 1_000_000_007];
+    let n = graph.len();
+    let reversed = internal_reverse(graph);
+    let (from_s, count_s0) = dijkstra_count(graph, &[s], MODS[0]);
+    let (to_t, count_t0) = dijkstra_count(&reversed, &[t], MODS[0]);
+    let count_s1 = dijkstra_count(graph, &[s], MODS[1]).1;
+    let count_t1 = dijkstra_count(&reversed, &[t], MODS[1]).1;
+    let total = from_s.dist[t]?;
+    let is_every = |u: usize, v: usize| {
+        count_s0[u] * count_t0[v] % MODS[0] == count_s0[t]
+            && count_s1[u] * count_t1[v] % MODS[1] == count_s1[t]
+    };
+
+    let mut vertex_some = vec![false; n];
+    let mut vertex_every = vec![false; n];
+    for v in 0..n {
+        if let (Some(a), Some(b)) = (from_s.dist[v], to_t.dist[v]) {
+            if a.plus(b) == total {
+                vertex_some[v] = true;
+                vertex_every[v] = is_every(v, v);
+            }
+        }
+    }
+    let m = internal_edge_id_bound(graph);
+    let mut edge_some = vec![false; m];
+    let mut edge_every = vec![false; m];
+    for u in 0..n {
+        let a = match from_s.dist[u] {
+            Some(a) => a,
+            None => continue,
+        };
+        for (&(to, w), &id) in graph.adj(u).iter().zip(graph.edge_ids(u)) {
+            if matches!(to_t.dist[to], Some(b) if a.plus(w).plus(b) == total) {
+                edge_some[id] = true;
+                edge_every[id] |= is_every(u, to);
+            }
+        }
+    }
+    Some(ShortestPathUsage {
+        vertex_some,
+        vertex_every,
+        edge_some,
+        edge_every,
+    })
+}
+
+//辺の向きを逆にしたグラフ(辺番号は引き継がない)
+fn internal_reverse<T>(graph: &Csr<T>) -> Csr<T>
+where
+    T: Copy,
+{
+    let mut edges = vec![];
+    for v in 0..graph.len() {
+        edges.extend(graph.adj(v).iter().map(|&(to, w)| (to, v, w)));
+    }
+    Csr::directed(graph.len(), &edges)
+}
+
+fn internal_edge_id_bound<T>(graph: &Csr<T>) -> usize {
+    (0..graph.len())
+        .flat_map(|v| graph.edge_ids(v).iter().map(|&id| id + 1))
+        .max()
+        .unwrap_or(0)
+}
+
+//禁止された頂点・辺を使わずにsからtへ行く最短経路と、経路上の各頂点までのコストを求める。
+fn internal_restricted_dijkstra<T>(
+    graph: &Csr<T>,
+    s: usize,
+    t: usize,
+    banned_vertex: &[bool],
+    banned_edge: &[bool],
+) -> Option<(KPath<T>, Vec<T>)>
+where
+    T: Cost,
+{
+    let n = graph.len();
+    let mut dist: Vec<Option<T>> = vec![None; n];
+    let mut prev = vec![None; n];
+    let mut heap = BinaryHeap::new();
+    dist[s] = Some(T::zero());
+    heap.push(Reverse((T::zero(), s)));
+    while let Some(Reverse((d, v))) = heap.pop() {
+        if dist[v] != Some(d) {
+            continue;
+        }
+        if v == t {
+            break;
+        }
+        for (&(to, w), &id) in graph.adj(v).iter().zip(graph.edge_ids(v)) {
+            if banned_vertex[to] || banned_edge[id] {
+                continue;
+            }
+            let nd = d.plus(w);
+            if !matches!(dist[to], Some(x) if x <= nd) {
+                dist[to] = Some(nd);
+                prev[to] = Some((v, id));
+                heap.push(Reverse((nd, to)));
+            }
+        }
+    }
+
+    let cost = dist[t]?;
+    let mut vertices = vec![t];
+    let mut edges = vec![];
+    let mut v = t;
+    while let Some((p, id)) = prev[v] {
+        vertices.push(p);
+        edges.push(id);
+        v = p;
+    }
+    vertices.reverse();
+    edges.reverse();
+    let prefix = vertices.iter().map(|&v| dist[v].unwrap()).collect();
+    Some((
+        KPath {
+            cost,
+            vertices,
+            edges,
+        },
+        prefix,
+    ))
+}
+
+struct PersistentHeapNode<T> {
+    key: T,
+    to: usize,
+    left: usize,
+    right: usize,
+    rank: usize,
+}
+
+//永続左偏ヒープ(ノードは配列で管理する)
+struct PersistentHeap<T> {
+    nodes: Vec<PersistentHeapNode<T>>,
+}
+
+impl<T> PersistentHeap<T>
+where
+    T: Copy + Ord,
+{
+    const NIL: usize = usize::MAX;
+
+    fn new() -> PersistentHeap<T> {
+        PersistentHeap { nodes: vec![] }
+    }
+
+    fn key(&self, x: usize) -> T {
+        self.nodes[x].key
+    }
+
+    fn rank(&self, x: usize) -> usize {
+        if x == Self::NIL {
+            0
+        } else {
+            self.nodes[x].rank
+        }
+    }
+
+    fn insert(&mut self, h: usize, key: T, to: usize) -> usize {
+        self.nodes.push(PersistentHeapNode {
+            key,
+            to,
+            left: Self::NIL,
+            right: Self::NIL,
+            rank: 1,
+        });
+        let x = self.nodes.len() - 1;
+        self.merge(h, x)
+    }
+
+    //aとbを併合した新しいヒープを返す。(a,bは変更しない)
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        if a == Self::NIL {
+            return b;
+        }
+        if b == Self::NIL {
+            return a;
+        }
+        let (a, b) = if self.nodes[b].key < self.nodes[a].key {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        let right = self.merge(self.nodes[a].right, b);
+        let mut left = self.nodes[a].left;
+        let mut right = right;
+        if self.rank(left) < self.rank(right) {
+            std::mem::swap(&mut left, &mut right);
+        }
+        let node = PersistentHeapNode {
+            key: self.nodes[a].key,
+            to: self.nodes[a].to,
+            left,
+            right,
+            rank: self.rank(right) + 1,
+        };
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn k_shortest_path_works() {
+        let edges = [
+            (0, 1, 3),
+            (0, 2, 2),
+            (1, 3, 4),
+            (2, 1, 1),
+            (2, 3, 2),
+            (2, 4, 3),
+            (3, 4, 2),
+            (3, 5, 1),
+            (4, 5, 2),
+        ];
+        let graph = Csr::directed(6, &edges);
+        let paths = yen_k_shortest_paths(&graph, 0, 5, 10);
+        assert_eq!(
+            paths.iter().map(|p| p.cost).collect::<Vec<_>>(),
+            vec![5, 7, 8, 8, 8, 11, 11]
+        );
+        assert_eq!(paths[0].vertices, vec![0, 2, 3, 5]);
+        assert_eq!(paths[0].edges, vec![1, 4, 7]);
+
+        assert_eq!(k_shortest_walks(&graph, 0, 5, 3), vec![5, 7, 8]);
+        let cycle = Csr::directed(2, &[(0, 1, 1), (1, 0, 2)]);
+        assert_eq!(k_shortest_walks(&cycle, 0, 1, 3), vec![1, 4, 7]);
+        assert_eq!(yen_k_shortest_paths(&cycle, 0, 1, 3).len(), 1);
+
+        let dag = shortest_path_dag(&graph, &dijkstra_paths(&graph, &[0]).dist);
+        assert_eq!(dag[2], vec![(1, 3), (3, 4), (4, 5)]);
+        assert_eq!(dag[1], vec![]);
+
+        let usage = shortest_path_usage(&graph, 0, 4).unwrap();
+        assert_eq!(
+            usage.vertex_every,
+            vec![true, false, true, false, true, false]
+        );
+        assert!(usage.edge_every[1] && usage.edge_every[5]);
+        let usage = shortest_path_usage(&graph, 0, 1).unwrap();
+        assert_eq!(
+            usage.vertex_some,
+            vec![true, true, true, false, false, false]
+        );
+        assert_eq!(
+            usage.vertex_every,
+            vec![true, true, false, false, false, false]
+        );
+        assert!(usage.edge_some[0] && usage.edge_some[1] && usage.edge_some[3]);
+        assert!(!usage.edge_every[0] && !usage.edge_some[4]);
+        assert!(shortest_path_usage(&graph, 5, 0).is_none());
+    }
+}
@@ -1,3 +1,5 @@
+use crate::csr::Csr;
+
 pub struct Lca {
     graph: Vec<Vec<usize>>,
     parent: Vec<Vec<usize>>,
@@ -9,13 +11,25 @@ pub struct Lca {
 
 impl Lca {
     pub fn new(v_size: usize, dfs_start: usize) -> Lca {
+        Lca::internal_new(vec![vec![]; v_size], v_size, dfs_start)
+    }
+
+    ///CSR形式の木から構築する。(calcを呼ぶ必要はない)
+    pub fn from_csr<W>(graph: &Csr<W>, dfs_start: usize) -> Lca {
+        //辺はgraphから直接読むので、隣接リストは確保しない。
+        let mut lca = Lca::internal_new(vec![], graph.len(), dfs_start);
+        lca.build(graph);
+        lca
+    }
+
+    fn internal_new(graph: Vec<Vec<usize>>, v_size: usize, dfs_start: usize) -> Lca {
         assert!(v_size > dfs_start);
         let mut bit_size = 0;
         while 1 << bit_size < v_size {
             bit_size += 1;
         }
         Lca {
-            graph: vec![vec![]; v_size],
+            graph,
             parent: vec![vec![dfs_start; v_size]; bit_size],
             depth: vec![0; v_size],
             bit_size,
@@ -32,11 +46,16 @@ impl Lca {
 
     pub fn calc(&mut self) {
         assert!(!self.preprocessing_complete);
+        let graph = Csr::from_unweighted(&self.graph);
+        self.build(&graph);
+    }
+
+    fn build<W>(&mut self, graph: &Csr<W>) {
         self.preprocessing_complete = true;
-        self.dfs(self.dfs_start, self.dfs_start, 0);
+        self.dfs(graph, self.dfs_start, self.dfs_start, 0);
 
         for i in 0..self.bit_size - 1 {
-            for j in 0..self.graph.len() {
+            for j in 0..graph.len() {
                 self.parent[i + 1][j] = self.parent[i][self.parent[i][j]];
             }
         }
@@ -67,14 +86,33 @@ impl Lca {
         self.depth[v]
     }
 
-    fn dfs(&mut self, n: usize, p: usize, d: usize) {
+    ///木の上でのvの親(根と、根から到達できない頂点はNone)
+    pub fn get_tree_parent(&self, v: usize) -> Option<usize> {
+        assert!(self.preprocessing_complete);
+        //深さが0になるのは根と、DFSで訪問しなかった頂点だけ
+        if self.depth[v] == 0 {
+            None
+        } else {
+            Some(self.parent[0][v])
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.depth.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.depth.is_empty()
+    }
+
+    fn dfs<W>(&mut self, graph: &Csr<W>, n: usize, p: usize, d: usize) {
         self.depth[n] = d;
         self.parent[0][n] = p;
-        for i in self.graph[n].clone() {
+        for i in graph.neighbors(n) {
             if i == p {
                 continue;
             }
-            self.dfs(i, n, d + 1);
+            self.dfs(graph, i, n, d + 1);
         }
     }
 
@@ -87,3 +125,29 @@ impl Lca {
         n
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lca_works() {
+        let edges = vec![(0, 1), (0, 2), (1, 3), (1, 4), (4, 5), (2, 6)];
+        let mut lca = Lca::new(7, 0);
+        for &(u, v) in &edges {
+            lca.add_edge(u, v);
+        }
+        lca.calc();
+        let from_csr = Lca::from_csr(&Csr::undirected_unweighted(7, &edges), 0);
+        assert_eq!(lca.get_lca(3, 5), 1);
+        assert_eq!(lca.get_lca(5, 6), 0);
+        assert_eq!(lca.get_lca(4, 5), 4);
+        assert_eq!(lca.get_depth(5), 3);
+        for u in 0..7 {
+            assert_eq!(lca.get_depth(u), from_csr.get_depth(u));
+            for v in 0..7 {
+                assert_eq!(lca.get_lca(u, v), from_csr.get_lca(u, v));
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::dinic::Dinic;
+
+///最小流量制約つきの流れ
+///
+///各辺の流量をlower以上upper以下に制限したときの循環流の存在判定、s-t最大流・最小流を求める。
+#[derive(Clone)]
+pub struct LowerBoundFlow {
+    n: usize,
+    graph: Dinic,
+    edges: Vec<(usize, i64)>,
+    excess: Vec<i64>,
+}
+
+impl LowerBoundFlow {
+    pub fn new(n: usize) -> LowerBoundFlow {
+        LowerBoundFlow {
+            n,
+            graph: Dinic::new(n + 2),
+            edges: vec![],
+            excess: vec![0; n],
+        }
+    }
+
+    ///流量がlower以上upper以下の辺from->toを追加し、辺番号を返す。
+    pub fn add_edge(&mut self, from: usize, to: usize, lower: i64, upper: i64) -> usize {
+        assert!(0 <= lower && lower <= upper);
+        let id = self.graph.add_edge(from, to, upper - lower);
+        self.excess[from] -= lower;
+        self.excess[to] += lower;
+        self.edges.push((id, lower));
+        self.edges.len() - 1
+    }
+
+    ///制約を満たす循環流が存在すれば、各辺の流量を返す。
+    pub fn circulation(&self) -> Option<Vec<i64>> {
+        let mut graph = self.graph.clone();
+        if !self.satisfy_demand(&mut graph) {
+            return None;
+        }
+        Some(self.edge_flows(&graph))
+    }
+
+    ///制約を満たすs-t流のうち流量最大のものを求める。
+    ///
+    ///(流量, 各辺の流量)を返す。制約を満たす流れが存在しなければNoneを返す。
+    pub fn max_flow(&self, s: usize, t: usize) -> Option<(i64, Vec<i64>)> {
+        let (mut graph, value) = self.feasible_st_flow(s, t)?;
+        let value = value + graph.flow(s, t);
+        Some((value, self.edge_flows(&graph)))
+    }
+
+    ///制約を満たすs-t流のうち流量最小のものを求める。
+    ///
+    ///(流量, 各辺の流量)を返す。制約を満たす流れが存在しなければNoneを返す。
+    pub fn min_flow(&self, s: usize, t: usize) -> Option<(i64, Vec<i64>)> {
+        let (mut graph, value) = self.feasible_st_flow(s, t)?;
+        let value = value - graph.flow(t, s);
+        Some((value, self.edge_flows(&graph)))
+    }
+
+    //s,t間に容量無限の辺を張って循環流を求め、その辺を取り除いたグラフとs-t流量を返す。
+    //sから流れ込む流れがあればs-t流量は負になりうる。
+    fn feasible_st_flow(&self, s: usize, t: usize) -> Option<(Dinic, i64)> {
+        const INF: i64 = i64::MAX / 4;
+        let mut graph = self.graph.clone();
+        let back = graph.add_edge(t, s, INF);
+        let forward = graph.add_edge(s, t, INF);
+        if !self.satisfy_demand(&mut graph) {
+            return None;
+        }
+        let value = graph.get_edge(back).3 - graph.get_edge(forward).3;
+        graph.change_edge(back, 0, 0);
+        graph.change_edge(forward, 0, 0);
+        Some((graph, value))
+    }
+
+    //超頂点を使って最小流量の分を流す。すべて流せればtrueを返す。
+    fn satisfy_demand(&self, graph: &mut Dinic) -> bool {
+        let (source, sink) = (self.n, self.n + 1);
+        let mut demand = 0;
+        for (v, &e) in self.excess.iter().enumerate() {
+            if e > 0 {
+                graph.add_edge(source, v, e);
+                demand += e;
+            } else if e < 0 {
+                graph.add_edge(v, sink, -e);
+            }
+        }
+        graph.flow(source, sink) == demand
+    }
+
+    fn edge_flows(&self, graph: &Dinic) -> Vec<i64> {
+        self.edges
+            .iter()
+            .map(|&(id, lower)| lower + graph.get_edge(id).3)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lower_bound_flow_works() {
+        let mut g = LowerBoundFlow::new(4);
+        g.add_edge(0, 1, 1, 3);
+        g.add_edge(0, 2, 0, 2);
+        g.add_edge(1, 3, 2, 2);
+        g.add_edge(2, 3, 1, 4);
+        g.add_edge(1, 2, 0, 1);
+
+        let (value, flows) = g.max_flow(0, 3).unwrap();
+        assert_eq!(value, 5);
+        assert_eq!(flows[0] + flows[1], 5);
+        let (value, flows) = g.min_flow(0, 3).unwrap();
+        assert_eq!(value, 3);
+        assert_eq!(flows[2], 2);
+        assert!(flows[3] >= 1);
+        assert!(g.circulation().is_none());
+
+        let mut g = LowerBoundFlow::new(3);
+        g.add_edge(0, 1, 1, 2);
+        g.add_edge(1, 2, 2, 3);
+        g.add_edge(2, 0, 0, 5);
+        assert_eq!(g.circulation(), Some(vec![2, 2, 2]));
+    }
+}
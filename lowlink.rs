@@ -0,0 +1,220 @@
+///無向グラフの橋・関節点・二重連結成分(lowlink)
+///
+///graph:グラフ情報(隣接リスト、無向辺は両方向に入れる。多重辺可)
+pub struct LowLink {
+    ord: Vec<usize>,
+    parent: Vec<usize>,
+    bridge_child: Vec<bool>,
+    bridges: Vec<(usize, usize)>,
+    articulation_points: Vec<usize>,
+    blocks: Vec<Vec<usize>>,
+}
+
+impl LowLink {
+    pub fn new(graph: &[Vec<usize>]) -> LowLink {
+        let n = graph.len();
+        let mut ord = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut parent = vec![usize::MAX; n];
+        let mut bridge_child = vec![false; n];
+        let mut is_articulation = vec![false; n];
+        let mut bridges = vec![];
+        let mut blocks = vec![];
+        let mut vertex_stack = vec![];
+        //(頂点, 次に見る辺の番号, 親への辺を一度読み飛ばしたか)
+        let mut call: Vec<(usize, usize, bool)> = vec![];
+        let mut time = 0;
+
+        for s in 0..n {
+            if ord[s] != usize::MAX {
+                continue;
+            }
+            ord[s] = time;
+            low[s] = time;
+            time += 1;
+            vertex_stack.push(s);
+            call.push((s, 0, false));
+            let mut root_children = 0;
+
+            while let Some(&mut (v, ref mut idx, ref mut skipped)) = call.last_mut() {
+                if *idx < graph[v].len() {
+                    let to = graph[v][*idx];
+                    *idx += 1;
+                    //多重辺に対応するため、親への辺は一本だけ無視する。
+                    if to == parent[v] && !*skipped {
+                        *skipped = true;
+                        continue;
+                    }
+                    if ord[to] == usize::MAX {
+                        ord[to] = time;
+                        low[to] = time;
+                        time += 1;
+                        parent[to] = v;
+                        vertex_stack.push(to);
+                        call.push((to, 0, false));
+                    } else {
+                        low[v] = low[v].min(ord[to]);
+                    }
+                    continue;
+                }
+
+                call.pop();
+                let p = parent[v];
+                if p == usize::MAX {
+                    continue;
+                }
+                low[p] = low[p].min(low[v]);
+                if low[v] > ord[p] {
+                    bridge_child[v] = true;
+                    bridges.push((p.min(v), p.max(v)));
+                }
+                if low[v] >= ord[p] {
+                    if parent[p] == usize::MAX {
+                        root_children += 1;
+                    } else {
+                        is_articulation[p] = true;
+                    }
+                    let mut block = vec![p];
+                    loop {
+                        let u = vertex_stack.pop().unwrap();
+                        block.push(u);
+                        if u == v {
+                            break;
+                        }
+                    }
+                    blocks.push(block);
+                }
+            }
+
+            vertex_stack.pop();
+            if root_children >= 2 {
+                is_articulation[s] = true;
+            }
+            if root_children == 0 {
+                blocks.push(vec![s]);
+            }
+        }
+
+        let articulation_points = (0..n).filter(|&i| is_articulation[i]).collect();
+        LowLink {
+            ord,
+            parent,
+            bridge_child,
+            bridges,
+            articulation_points,
+            blocks,
+        }
+    }
+
+    ///橋を(小さい頂点, 大きい頂点)の形で返す。
+    pub fn bridges(&self) -> &[(usize, usize)] {
+        &self.bridges
+    }
+
+    ///関節点を昇順で返す。
+    pub fn articulation_points(&self) -> &[usize] {
+        &self.articulation_points
+    }
+
+    ///二重辺連結成分分解
+    ///
+    ///(成分数, 各頂点の成分番号)を返す。
+    pub fn two_edge_connected_components(&self) -> (usize, Vec<usize>) {
+        let n = self.ord.len();
+        let mut vertices = (0..n).collect::<Vec<_>>();
+        vertices.sort_unstable_by_key(|&v| self.ord[v]);
+        let mut ids = vec![0; n];
+        let mut count = 0;
+        for v in vertices {
+            if self.parent[v] == usize::MAX || self.bridge_child[v] {
+                ids[v] = count;
+                count += 1;
+            } else {
+                ids[v] = ids[self.parent[v]];
+            }
+        }
+        (count, ids)
+    }
+
+    ///二重辺連結成分を頂点とし、橋を辺とする森(隣接リスト)を返す。
+    pub fn bridge_tree(&self) -> Vec<Vec<usize>> {
+        let (count, ids) = self.two_edge_connected_components();
+        let mut r = vec![vec![]; count];
+        for &(u, v) in &self.bridges {
+            r[ids[u]].push(ids[v]);
+            r[ids[v]].push(ids[u]);
+        }
+        r
+    }
+
+    ///二重頂点連結成分(ブロック)ごとの頂点リストを返す。孤立点も一つのブロックになる。
+    pub fn blocks(&self) -> &[Vec<usize>] {
+        &self.blocks
+    }
+
+    ///block-cut tree
+    ///
+    ///頂点0..nは元の頂点、n+iはi番目のブロックを表し、各頂点と所属するブロックを辺で結ぶ。
+    pub fn block_cut_tree(&self) -> Vec<Vec<usize>> {
+        let n = self.ord.len();
+        let mut r = vec![vec![]; n + self.blocks.len()];
+        for (i, block) in self.blocks.iter().enumerate() {
+            for &v in block {
+                r[v].push(n + i);
+                r[n + i].push(v);
+            }
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn undirected(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+        let mut graph = vec![vec![]; n];
+        for &(u, v) in edges {
+            graph[u].push(v);
+            graph[v].push(u);
+        }
+        graph
+    }
+
+    #[test]
+    fn lowlink_works() {
+        //0-1-2の三角形, 2-3の橋, 3-4の多重辺, 4-5の橋, 孤立点6
+        let graph = undirected(7, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3), (4, 5)]);
+        let lowlink = LowLink::new(&graph);
+        let mut bridges = lowlink.bridges().to_vec();
+        bridges.sort_unstable();
+        assert_eq!(bridges, vec![(2, 3), (4, 5)]);
+        assert_eq!(lowlink.articulation_points(), &[2, 3, 4]);
+
+        let (count, ids) = lowlink.two_edge_connected_components();
+        assert_eq!(count, 4);
+        assert_eq!(ids[0], ids[1]);
+        assert_eq!(ids[1], ids[2]);
+        assert_eq!(ids[3], ids[4]);
+        assert_ne!(ids[2], ids[3]);
+        assert_ne!(ids[4], ids[5]);
+        let tree = lowlink.bridge_tree();
+        assert_eq!(tree.iter().map(|v| v.len()).sum::<usize>(), 4);
+
+        let mut blocks = lowlink
+            .blocks()
+            .iter()
+            .map(|b| {
+                let mut b = b.clone();
+                b.sort_unstable();
+                b
+            })
+            .collect::<Vec<_>>();
+        blocks.sort();
+        assert_eq!(
+            blocks,
+            vec![vec![0, 1, 2], vec![2, 3], vec![3, 4], vec![4, 5], vec![6]]
+        );
+        assert_eq!(lowlink.block_cut_tree().len(), 7 + 5);
+    }
+}
@@ -0,0 +1,222 @@
+use crate::dinic::Dinic;
+
+///無向グラフの全域最小カット(Stoer-Wagner法、O(n^3))
+///
+///n:頂点数(2以上), edges:(u, v, 重み)のリスト(重みは非負)
+///
+///(カットの重み, 片側の頂点をtrueとした配列)を返す。
+pub fn stoer_wagner(n: usize, edges: &[(usize, usize, i64)]) -> (i64, Vec<bool>) {
+    assert!(n >= 2);
+    let mut w = vec![vec![0; n]; n];
+    for &(u, v, c) in edges {
+        assert!(c >= 0);
+        if u != v {
+            w[u][v] += c;
+            w[v][u] += c;
+        }
+    }
+    //縮約された頂点が元のどの頂点の集まりか
+    let mut merged = (0..n).map(|v| vec![v]).collect::<Vec<_>>();
+    let mut alive = (0..n).collect::<Vec<_>>();
+    let mut best = (i64::MAX, vec![]);
+    while alive.len() > 1 {
+        let mut in_a = vec![false; n];
+        let mut key = vec![0; n];
+        let (mut s, mut t) = (alive[0], alive[0]);
+        for _ in 0..alive.len() {
+            let v = *alive
+                .iter()
+                .filter(|&&v| !in_a[v])
+                .max_by_key(|&&v| key[v])
+                .unwrap();
+            in_a[v] = true;
+            s = t;
+            t = v;
+            for &u in &alive {
+                if !in_a[u] {
+                    key[u] += w[v][u];
+                }
+            }
+        }
+        if key[t] < best.0 {
+            best = (key[t], merged[t].clone());
+        }
+        //tをsに縮約する。
+        for &u in &alive {
+            w[s][u] += w[t][u];
+            w[u][s] = w[s][u];
+        }
+        w[s][s] = 0;
+        let mt = std::mem::take(&mut merged[t]);
+        merged[s].extend(mt);
+        alive.retain(|&v| v != t);
+    }
+    let mut side = vec![false; n];
+    for &v in &best.1 {
+        side[v] = true;
+    }
+    (best.0, side)
+}
+
+///Gomory-Hu木(Gusfieldのアルゴリズム、最大流n-1回)
+///
+///任意の2頂点間の最小カットの重みは、木の上のパスにある辺の重みの最小値と等しい。
+pub struct GomoryHuTree {
+    parent: Vec<Option<usize>>,
+    weight: Vec<i64>,
+    depth: Vec<usize>,
+    children: Vec<Vec<usize>>,
+}
+
+impl GomoryHuTree {
+    ///n:頂点数, edges:(u, v, 重み)のリスト(重みは非負)
+    pub fn new(n: usize, edges: &[(usize, usize, i64)]) -> GomoryHuTree {
+        let mut base = Dinic::new(n);
+        for &(u, v, c) in edges {
+            base.add_edge(u, v, c);
+            base.add_edge(v, u, c);
+        }
+        let mut p = vec![0; n];
+        let mut fl = vec![0; n];
+        for s in 1..n {
+            let t = p[s];
+            let mut graph = base.clone();
+            let value = graph.flow(s, t);
+            let x = graph.min_cut(s);
+            fl[s] = value;
+            for i in 0..n {
+                if i != s && x[i] && p[i] == t {
+                    p[i] = s;
+                }
+            }
+            if x[p[t]] {
+                p[s] = p[t];
+                p[t] = s;
+                fl[s] = fl[t];
+                fl[t] = value;
+            }
+        }
+
+        //根を0とする木を作る。
+        let mut parent = vec![None; n];
+        let mut children = vec![vec![]; n];
+        for v in 0..n {
+            if p[v] != v {
+                parent[v] = Some(p[v]);
+                children[p[v]].push(v);
+            }
+        }
+        let mut depth = vec![0; n];
+        let mut stack = (0..n).filter(|&v| parent[v].is_none()).collect::<Vec<_>>();
+        while let Some(v) = stack.pop() {
+            for &c in &children[v] {
+                depth[c] = depth[v] + 1;
+                stack.push(c);
+            }
+        }
+        GomoryHuTree {
+            parent,
+            weight: fl,
+            depth,
+            children,
+        }
+    }
+
+    ///木の辺(子, 親, 重み)のリストを返す。
+    pub fn edges(&self) -> Vec<(usize, usize, i64)> {
+        (0..self.parent.len())
+            .filter_map(|v| self.parent[v].map(|p| (v, p, self.weight[v])))
+            .collect()
+    }
+
+    ///uとvの間の最小カットの重み(O(n))
+    pub fn min_cut(&self, u: usize, v: usize) -> i64 {
+        assert_ne!(u, v);
+        self.weight[self.min_edge(u, v)]
+    }
+
+    ///uとvの間の最小カットの(重み, uの側の頂点をtrueとした配列)を返す。
+    pub fn cut(&self, u: usize, v: usize) -> (i64, Vec<bool>) {
+        assert_ne!(u, v);
+        let c = self.min_edge(u, v);
+        //cの部分木が片側になる。
+        let mut side = vec![false; self.parent.len()];
+        let mut stack = vec![c];
+        side[c] = true;
+        while let Some(x) = stack.pop() {
+            for &y in &self.children[x] {
+                side[y] = true;
+                stack.push(y);
+            }
+        }
+        if !side[u] {
+            side.iter_mut().for_each(|x| *x = !*x);
+        }
+        (self.weight[c], side)
+    }
+
+    //u-vパス上で重み最小の辺を、その子側の頂点で返す。
+    fn min_edge(&self, mut u: usize, mut v: usize) -> usize {
+        let mut best = None;
+        let mut update = |c: usize| {
+            if !matches!(best, Some(b) if self.weight[b] <= self.weight[c]) {
+                best = Some(c);
+            }
+        };
+        while u != v {
+            if self.depth[u] < self.depth[v] {
+                std::mem::swap(&mut u, &mut v);
+            }
+            update(u);
+            u = self.parent[u].unwrap();
+        }
+        best.unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mincut_works() {
+        let edges = [
+            (0, 1, 2),
+            (0, 4, 3),
+            (1, 2, 3),
+            (1, 4, 2),
+            (1, 5, 2),
+            (2, 3, 4),
+            (2, 6, 2),
+            (3, 6, 2),
+            (3, 7, 2),
+            (4, 5, 3),
+            (5, 6, 1),
+            (6, 7, 3),
+        ];
+        let (value, side) = stoer_wagner(8, &edges);
+        assert_eq!(value, 4);
+        assert_eq!(
+            side,
+            if side[0] {
+                vec![true, true, false, false, true, true, false, false]
+            } else {
+                vec![false, false, true, true, false, false, true, true]
+            }
+        );
+
+        let tree = GomoryHuTree::new(8, &edges);
+        assert_eq!(tree.edges().len(), 7);
+        assert_eq!(tree.min_cut(0, 7), 4);
+        assert_eq!(tree.min_cut(2, 3), 7);
+        let (value, side) = tree.cut(0, 4);
+        assert_eq!(value, 5);
+        let crossing = edges
+            .iter()
+            .filter(|&&(u, v, _)| side[u] != side[v])
+            .map(|e| e.2)
+            .sum::<i64>();
+        assert_eq!(crossing, 5);
+        assert!(side[0] && !side[4]);
+    }
+}
@@ -0,0 +1,333 @@
+use crate::csr::Csr;
+use crate::dsu::Dsu;
+use crate::graph::Cost;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::ops::Sub;
+
+///最小全域木(森)の計算結果
+pub struct Mst<T> {
+    ///重みの総和
+    pub cost: T,
+    ///選んだ辺の辺番号
+    pub edges: Vec<usize>,
+}
+
+///最小全域木(クラスカル法)
+///
+///n:頂点数
+///
+///edges:辺リスト(u, v, cost)(添字を辺番号とする)
+///
+///グラフが非連結の場合は最小全域森を返す。
+pub fn kruskal<T>(n: usize, edges: &[(usize, usize, T)]) -> Mst<T>
+where
+    T: Cost,
+{
+    let mut order = (0..edges.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| edges[i].2);
+    let mut dsu = Dsu::new(n);
+    let mut cost = T::zero();
+    let mut r = vec![];
+    for i in order {
+        let (u, v, c) = edges[i];
+        if dsu.same(u, v) {
+            continue;
+        }
+        dsu.merge(u, v);
+        cost = cost.plus(c);
+        r.push(i);
+    }
+    Mst { cost, edges: r }
+}
+
+///最小全域木(プリム法)
+///
+///graph:無向グラフ(Csr::undirectedで構築したもの)
+///
+///グラフが非連結の場合は最小全域森を返す。辺番号はCsrの辺番号になる。
+pub fn prim<T>(graph: &Csr<T>) -> Mst<T>
+where
+    T: Cost,
+{
+    let n = graph.len();
+    let mut used = vec![false; n];
+    let mut cost = T::zero();
+    let mut r = vec![];
+    let mut heap = BinaryHeap::new();
+    for s in 0..n {
+        if used[s] {
+            continue;
+        }
+        heap.push(Reverse((T::zero(), s, usize::MAX)));
+        while let Some(Reverse((c, v, id))) = heap.pop() {
+            if used[v] {
+                continue;
+            }
+            used[v] = true;
+            if id != usize::MAX {
+                cost = cost.plus(c);
+                r.push(id);
+            }
+            for (&(to, c), &id) in graph.adj(v).iter().zip(graph.edge_ids(v)) {
+                if !used[to] {
+                    heap.push(Reverse((c, to, id)));
+                }
+            }
+        }
+    }
+    Mst { cost, edges: r }
+}
+
+///最小全域木(プリム法、graph.rsの隣接リスト形式)
+///
+///graph:無向グラフの隣接リスト(各辺を両方向に持つ)
+///
+///辺番号は隣接リストを順に読んだ順になる。(Csr::from_adjacencyと同じ)
+pub fn prim_adjacency<T>(graph: &[Vec<(usize, T)>]) -> Mst<T>
+where
+    T: Cost,
+{
+    prim(&Csr::from_adjacency(graph))
+}
+
+///最小全域木(ブルーフカ法)
+///
+///辺を陽に持てない(完全グラフなど)場合に使う。
+///
+///cheapest:各頂点の連結成分番号を受け取り、各頂点vについて別の成分の頂点へ向かう最小コストの辺(行き先, コスト)を返す関数
+///
+///(重みの総和, 選んだ辺(u, v, cost)のリスト)を返す。
+pub fn boruvka<T, F>(n: usize, mut cheapest: F) -> (T, Vec<(usize, usize, T)>)
+where
+    T: Cost,
+    F: FnMut(&[usize]) -> Vec<Option<(usize, T)>>,
+{
+    let mut dsu = Dsu::new(n);
+    let mut cost = T::zero();
+    let mut r = vec![];
+    loop {
+        let comp = (0..n).map(|v| dsu.leader(v)).collect::<Vec<_>>();
+        let mut best: Vec<Option<(T, usize, usize)>> = vec![None; n];
+        for (v, e) in cheapest(&comp).into_iter().enumerate() {
+            if let Some((to, c)) = e {
+                let b = &mut best[comp[v]];
+                if !matches!(*b, Some(x) if x <= (c, v, to)) {
+                    *b = Some((c, v, to));
+                }
+            }
+        }
+        let mut merged = false;
+        for &(c, u, v) in best.iter().flatten() {
+            if dsu.same(u, v) {
+                continue;
+            }
+            dsu.merge(u, v);
+            cost = cost.plus(c);
+            r.push((u, v, c));
+            merged = true;
+        }
+        if !merged {
+            break;
+        }
+    }
+    (cost, r)
+}
+
+///2番目に小さい全域木の重み(最小全域木と辺集合が異なるもののうち最小)
+///
+///グラフが非連結の場合や全域木が一つしかない場合はNoneを返す。
+pub fn second_best_mst<T>(n: usize, edges: &[(usize, usize, T)]) -> Option<T>
+where
+    T: Cost + Sub<Output = T>,
+{
+    let mst = kruskal(n, edges);
+    if mst.edges.len() + 1 != n {
+        return None;
+    }
+    let tree = MaxPathTree::new(n, edges, &mst.edges);
+    let mut in_tree = vec![false; edges.len()];
+    for &i in &mst.edges {
+        in_tree[i] = true;
+    }
+    let mut r: Option<T> = None;
+    for (i, &(u, v, c)) in edges.iter().enumerate() {
+        if in_tree[i] || u == v {
+            continue;
+        }
+        let cand = mst.cost.plus(c) - tree.max_on_path(u, v).unwrap();
+        if !matches!(r, Some(x) if x <= cand) {
+            r = Some(cand);
+        }
+    }
+    r
+}
+
+///各辺がいずれかの最小全域木(森)に含まれるかどうかを返す。
+pub fn mst_edge_classification<T>(n: usize, edges: &[(usize, usize, T)]) -> Vec<bool>
+where
+    T: Cost,
+{
+    let mst = kruskal(n, edges);
+    let tree = MaxPathTree::new(n, edges, &mst.edges);
+    let mut r = vec![false; edges.len()];
+    for &i in &mst.edges {
+        r[i] = true;
+    }
+    for (i, &(u, v, c)) in edges.iter().enumerate() {
+        if !r[i] && u != v {
+            //木上のパスの最大の辺と同じ重みなら入れ替えられる。
+            r[i] = matches!(tree.max_on_path(u, v), Some(x) if x >= c);
+        }
+    }
+    r
+}
+
+//木上のパスの最大の重みをダブリングで求める。
+struct MaxPathTree<T> {
+    depth: Vec<usize>,
+    root: Vec<usize>,
+    parent: Vec<Vec<usize>>,
+    max: Vec<Vec<Option<T>>>,
+}
+
+impl<T> MaxPathTree<T>
+where
+    T: Cost,
+{
+    fn new(n: usize, edges: &[(usize, usize, T)], tree_edges: &[usize]) -> MaxPathTree<T> {
+        let mut graph = vec![vec![]; n];
+        for &i in tree_edges {
+            let (u, v, c) = edges[i];
+            graph[u].push((v, c));
+            graph[v].push((u, c));
+        }
+        let mut bit_size = 1;
+        while 1 << bit_size < n {
+            bit_size += 1;
+        }
+        let mut depth = vec![0; n];
+        let mut root = vec![usize::MAX; n];
+        let mut parent = vec![(0..n).collect::<Vec<_>>(); bit_size];
+        let mut max = vec![vec![None; n]; bit_size];
+        for s in 0..n {
+            if root[s] != usize::MAX {
+                continue;
+            }
+            root[s] = s;
+            let mut stack = vec![s];
+            while let Some(v) = stack.pop() {
+                for &(to, c) in &graph[v] {
+                    if root[to] != usize::MAX {
+                        continue;
+                    }
+                    root[to] = s;
+                    depth[to] = depth[v] + 1;
+                    parent[0][to] = v;
+                    max[0][to] = Some(c);
+                    stack.push(to);
+                }
+            }
+        }
+        for i in 0..bit_size - 1 {
+            for v in 0..n {
+                let p = parent[i][v];
+                parent[i + 1][v] = parent[i][p];
+                max[i + 1][v] = std::cmp::max(max[i][v], max[i][p]);
+            }
+        }
+        MaxPathTree {
+            depth,
+            root,
+            parent,
+            max,
+        }
+    }
+
+    //u,vが別の木にある場合はNone
+    fn max_on_path(&self, mut u: usize, mut v: usize) -> Option<T> {
+        if self.root[u] != self.root[v] {
+            return None;
+        }
+        if self.depth[u] < self.depth[v] {
+            std::mem::swap(&mut u, &mut v);
+        }
+        let mut r = None;
+        let diff = self.depth[u] - self.depth[v];
+        for i in 0..self.parent.len() {
+            if (diff >> i) & 1 == 1 {
+                r = std::cmp::max(r, self.max[i][u]);
+                u = self.parent[i][u];
+            }
+        }
+        if u == v {
+            return r;
+        }
+        for i in (0..self.parent.len()).rev() {
+            if self.parent[i][u] != self.parent[i][v] {
+                r = std::cmp::max(r, std::cmp::max(self.max[i][u], self.max[i][v]));
+                u = self.parent[i][u];
+                v = self.parent[i][v];
+            }
+        }
+        std::cmp::max(r, std::cmp::max(self.max[0][u], self.max[0][v]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mst_works() {
+        let edges = vec![
+            (0, 1, 1),
+            (1, 2, 2),
+            (0, 2, 2),
+            (2, 3, 5),
+            (1, 3, 7),
+            (3, 4, 1),
+        ];
+        let mst = kruskal(5, &edges);
+        assert_eq!(mst.cost, 9);
+        assert_eq!(mst.edges.len(), 4);
+
+        let mst = prim(&Csr::undirected(5, &edges));
+        assert_eq!(mst.cost, 9);
+        let mut ids = mst.edges.clone();
+        ids.sort_unstable();
+        assert!(ids == vec![0, 1, 3, 5] || ids == vec![0, 2, 3, 5]);
+
+        let mut graph = vec![vec![]; 5];
+        for &(u, v, c) in &edges {
+            graph[u].push((v, c));
+            graph[v].push((u, c));
+        }
+        let mst = prim_adjacency(&graph);
+        assert_eq!(mst.cost, 9);
+        assert_eq!(mst.edges.len(), 4);
+
+        let points = [0_i64, 3, 4, 10, 11];
+        let (cost, chosen) = boruvka(points.len(), |comp| {
+            (0..points.len())
+                .map(|v| {
+                    (0..points.len())
+                        .filter(|&u| comp[u] != comp[v])
+                        .map(|u| (u, (points[u] - points[v]).abs()))
+                        .min_by_key(|e| e.1)
+                })
+                .collect()
+        });
+        assert_eq!(cost, 11);
+        assert_eq!(chosen.len(), 4);
+
+        assert_eq!(second_best_mst(5, &edges), Some(9));
+        assert_eq!(
+            mst_edge_classification(5, &edges),
+            vec![true, true, true, true, false, true]
+        );
+        let edges = vec![(0, 1, 1), (1, 2, 2), (0, 2, 3)];
+        assert_eq!(second_best_mst(3, &edges), Some(4));
+        assert_eq!(second_best_mst(4, &edges), None);
+    }
+}
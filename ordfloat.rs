@@ -1,7 +1,7 @@
 use std::cmp::Ordering;
 use std::ops::Deref;
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct OrdFloat(pub f64);
 
 impl Ord for OrdFloat {
@@ -143,7 +143,7 @@ impl SmallRng for PCG64 {
             std::ops::Bound::Unbounded => i64::MAX,
         };
 
-        start + self.next_i64() % (end - start)
+        start + self.next_i64().rem_euclid(end - start)
     }
 
     fn range_usize<R>(&mut self, r: R) -> usize
@@ -179,6 +179,6 @@ impl SmallRng for PCG64 {
             std::ops::Bound::Unbounded => isize::MAX,
         };
 
-        start + self.next_isize() % (end - start)
+        start + self.next_isize().rem_euclid(end - start)
     }
 }
@@ -0,0 +1,345 @@
+use crate::{Shuffle, SmallRng};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+use std::ops::RangeBounds;
+
+///Prüfer列から木を復元する。
+///
+///code:長さn-2で各要素がn未満の列
+pub fn prufer_decode(code: &[usize]) -> Vec<(usize, usize)> {
+    let n = code.len() + 2;
+    let mut degree = vec![1; n];
+    for &v in code {
+        degree[v] += 1;
+    }
+    let mut leaves = (0..n)
+        .filter(|&v| degree[v] == 1)
+        .map(Reverse)
+        .collect::<BinaryHeap<_>>();
+    let mut edges = Vec::with_capacity(n - 1);
+    for &v in code {
+        let Reverse(leaf) = leaves.pop().unwrap();
+        edges.push((leaf, v));
+        degree[v] -= 1;
+        if degree[v] == 1 {
+            leaves.push(Reverse(v));
+        }
+    }
+    let Reverse(u) = leaves.pop().unwrap();
+    let Reverse(v) = leaves.pop().unwrap();
+    edges.push((u, v));
+    edges
+}
+
+///一様ランダムなラベルつき木(Prüfer列から生成)
+pub fn random_tree<R>(n: usize, rng: &mut R) -> Vec<(usize, usize)>
+where
+    R: SmallRng,
+{
+    if n <= 1 {
+        return vec![];
+    }
+    let code = (0..n - 2)
+        .map(|_| rng.range_usize(0..n))
+        .collect::<Vec<_>>();
+    prufer_decode(&code)
+}
+
+///頂点の順番がランダムなパスグラフ
+pub fn random_path<R>(n: usize, rng: &mut R) -> Vec<(usize, usize)>
+where
+    R: SmallRng,
+{
+    let order = random_permutation(n, rng);
+    order.windows(2).map(|w| (w[0], w[1])).collect()
+}
+
+///中心がランダムなスターグラフ
+pub fn random_star<R>(n: usize, rng: &mut R) -> Vec<(usize, usize)>
+where
+    R: SmallRng,
+{
+    if n <= 1 {
+        return vec![];
+    }
+    let center = rng.range_usize(0..n);
+    (0..n)
+        .filter(|&v| v != center)
+        .map(|v| (center, v))
+        .collect()
+}
+
+///毛虫グラフ(長さspineのパスに、残りの頂点を葉としてつないだ木)
+pub fn random_caterpillar<R>(n: usize, spine: usize, rng: &mut R) -> Vec<(usize, usize)>
+where
+    R: SmallRng,
+{
+    assert!(1 <= spine && spine <= n);
+    let order = random_permutation(n, rng);
+    let mut edges = order[..spine]
+        .windows(2)
+        .map(|w| (w[0], w[1]))
+        .collect::<Vec<_>>();
+    for &v in &order[spine..] {
+        edges.push((order[rng.range_usize(0..spine)], v));
+    }
+    edges
+}
+
+///辺がm本の単純無向グラフ(連結とは限らない)
+pub fn random_graph<R>(n: usize, m: usize, rng: &mut R) -> Vec<(usize, usize)>
+where
+    R: SmallRng,
+{
+    random_simple_edges(n, m, vec![], rng)
+}
+
+///辺がm本の連結な単純無向グラフ(n-1 <= m <= n(n-1)/2)
+pub fn random_connected_graph<R>(n: usize, m: usize, rng: &mut R) -> Vec<(usize, usize)>
+where
+    R: SmallRng,
+{
+    assert!(m + 1 >= n);
+    let tree = random_tree(n, rng);
+    let mut edges = random_simple_edges(n, m, tree, rng);
+    edges.shuffle(rng);
+    edges
+}
+
+///辺がm本の単純なDAG(頂点番号の順がトポロジカル順とは限らない)
+pub fn random_dag<R>(n: usize, m: usize, rng: &mut R) -> Vec<(usize, usize)>
+where
+    R: SmallRng,
+{
+    let order = random_permutation(n, rng);
+    random_simple_edges(n, m, vec![], rng)
+        .into_iter()
+        .map(|(u, v)| {
+            let (u, v) = if u < v { (u, v) } else { (v, u) };
+            (order[u], order[v])
+        })
+        .collect()
+}
+
+///左側left頂点、右側right頂点で辺がm本の単純な二部グラフ
+///
+///(左側の頂点, 右側の頂点)のリストを返す。
+pub fn random_bipartite<R>(left: usize, right: usize, m: usize, rng: &mut R) -> Vec<(usize, usize)>
+where
+    R: SmallRng,
+{
+    assert!(m <= left * right);
+    random_distinct(left * right, m, rng)
+        .into_iter()
+        .map(|x| (x / right, x % right))
+        .collect()
+}
+
+///辺リストに範囲rangeの一様ランダムな重みをつける。
+pub fn random_weights<R, T>(
+    edges: &[(usize, usize)],
+    range: T,
+    rng: &mut R,
+) -> Vec<(usize, usize, isize)>
+where
+    R: SmallRng,
+    T: RangeBounds<isize> + Clone,
+{
+    edges
+        .iter()
+        .map(|&(u, v)| (u, v, rng.range_isize(range.clone())))
+        .collect()
+}
+
+///h*wのグリッド(b'.'が通路、b'#'が障害物、grid.rsの関数にそのまま渡せる)
+///
+///各マスはwall_percent%の確率で障害物になる。
+pub fn random_grid<R>(h: usize, w: usize, wall_percent: u64, rng: &mut R) -> Vec<Vec<u8>>
+where
+    R: SmallRng,
+{
+    (0..h)
+        .map(|_| {
+            (0..w)
+                .map(|_| {
+                    if rng.range_u64(0..100) < wall_percent {
+                        b'#'
+                    } else {
+                        b'.'
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+///辺リストを隣接リスト(graph.rsの形式)に変換する。
+pub fn to_adjacency(n: usize, edges: &[(usize, usize)], directed: bool) -> Vec<Vec<usize>> {
+    let mut graph = vec![vec![]; n];
+    for &(u, v) in edges {
+        graph[u].push(v);
+        if !directed {
+            graph[v].push(u);
+        }
+    }
+    graph
+}
+
+///重みつきの辺リストを隣接リスト(graph.rsの形式)に変換する。
+pub fn to_weighted_adjacency<W>(
+    n: usize,
+    edges: &[(usize, usize, W)],
+    directed: bool,
+) -> Vec<Vec<(usize, W)>>
+where
+    W: Copy,
+{
+    let mut graph = vec![vec![]; n];
+    for &(u, v, w) in edges {
+        graph[u].push((v, w));
+        if !directed {
+            graph[v].push((u, w));
+        }
+    }
+    graph
+}
+
+fn random_permutation<R>(n: usize, rng: &mut R) -> Vec<usize>
+where
+    R: SmallRng,
+{
+    let mut p = (0..n).collect::<Vec<_>>();
+    p.shuffle(rng);
+    p
+}
+
+//0..nの中から相異なるm個をランダムに選ぶ。
+fn random_distinct<R>(n: usize, m: usize, rng: &mut R) -> Vec<usize>
+where
+    R: SmallRng,
+{
+    assert!(m <= n);
+    if m * 2 > n {
+        let mut all = random_permutation(n, rng);
+        all.truncate(m);
+        return all;
+    }
+    let mut used = HashSet::new();
+    let mut r = Vec::with_capacity(m);
+    while r.len() < m {
+        let x = rng.range_usize(0..n);
+        if used.insert(x) {
+            r.push(x);
+        }
+    }
+    r
+}
+
+//edgesに辺を加えて、m本の相異なる辺(自己ループなし)にする。
+fn random_simple_edges<R>(
+    n: usize,
+    m: usize,
+    mut edges: Vec<(usize, usize)>,
+    rng: &mut R,
+) -> Vec<(usize, usize)>
+where
+    R: SmallRng,
+{
+    let key = |u: usize, v: usize| if u < v { (u, v) } else { (v, u) };
+    let max = n * n.saturating_sub(1) / 2;
+    assert!(edges.len() <= m && m <= max);
+    let mut used = edges
+        .iter()
+        .map(|&(u, v)| key(u, v))
+        .collect::<HashSet<_>>();
+    if m * 2 > max {
+        //密なときは候補を列挙して選ぶ。
+        let mut cand = (0..n)
+            .flat_map(|u| (u + 1..n).map(move |v| (u, v)))
+            .filter(|p| !used.contains(p))
+            .collect::<Vec<_>>();
+        cand.shuffle(rng);
+        cand.truncate(m - edges.len());
+        edges.extend(cand);
+        return edges;
+    }
+    while edges.len() < m {
+        let u = rng.range_usize(0..n);
+        let v = rng.range_usize(0..n);
+        if u != v && used.insert(key(u, v)) {
+            edges.push((u, v));
+        }
+    }
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsu::Dsu;
+    use crate::XorShift;
+
+    fn is_connected(n: usize, edges: &[(usize, usize)]) -> bool {
+        let mut dsu = Dsu::new(n);
+        for &(u, v) in edges {
+            dsu.merge(u, v);
+        }
+        (0..n).all(|v| dsu.same(0, v))
+    }
+
+    #[test]
+    fn random_graph_works() {
+        let mut rng = XorShift::default();
+        assert_eq!(
+            prufer_decode(&[3, 3, 3]),
+            vec![(0, 3), (1, 3), (2, 3), (3, 4)]
+        );
+        for n in 1..10 {
+            for edges in [
+                random_tree(n, &mut rng),
+                random_path(n, &mut rng),
+                random_star(n, &mut rng),
+                random_caterpillar(n, n / 2 + 1, &mut rng),
+            ] {
+                assert_eq!(edges.len(), n - 1);
+                assert!(is_connected(n, &edges));
+            }
+
+            let max = n * (n - 1) / 2;
+            for m in n - 1..=max {
+                let edges = random_connected_graph(n, m, &mut rng);
+                assert_eq!(edges.len(), m);
+                assert!(is_connected(n, &edges));
+                let mut keys = edges
+                    .iter()
+                    .map(|&(u, v)| (u.min(v), u.max(v)))
+                    .collect::<Vec<_>>();
+                keys.sort_unstable();
+                keys.dedup();
+                assert_eq!(keys.len(), m);
+                assert!(edges.iter().all(|&(u, v)| u != v));
+            }
+
+            let dag = random_dag(n, max / 2, &mut rng);
+            assert_eq!(dag.len(), max / 2);
+            let graph = to_adjacency(n, &dag, true);
+            assert!(crate::graph_search::topological_sort_kahn(&graph).is_ok());
+        }
+
+        let edges = random_bipartite(3, 4, 12, &mut rng);
+        assert_eq!(edges.len(), 12);
+        assert!(edges.iter().all(|&(l, r)| l < 3 && r < 4));
+
+        let weighted = random_weights(&random_tree(5, &mut rng), -3..=3, &mut rng);
+        assert!(weighted.iter().all(|&(_, _, w)| (-3..=3).contains(&w)));
+        assert_eq!(to_weighted_adjacency(5, &weighted, false).concat().len(), 8);
+
+        let grid = random_grid(3, 5, 30, &mut rng);
+        assert_eq!(grid.len(), 3);
+        assert!(grid.iter().all(|row| row.len() == 5));
+        let grid = random_grid(2, 2, 0, &mut rng);
+        assert!(grid.concat().iter().all(|&c| c == b'.'));
+        let dist = crate::grid::grid_bfs(&grid, &[(0, 0)], &crate::grid::DIR4, |&c| c == b'.');
+        assert_eq!(dist[1][1], Some(2));
+    }
+}
@@ -72,6 +72,10 @@ mod tests {
             assert_eq!(i as isize, rng.range_isize(i as isize..=i as isize));
             assert_eq!(i as isize, rng.range_isize(i as isize..i as isize + 1));
         }
+        for _ in 0..100 {
+            assert!((-5..5).contains(&rng.range_i64(-5..5)));
+            assert!((-5..=-3).contains(&rng.range_isize(-5..=-3)));
+        }
     }
 
     #[test]
@@ -90,6 +94,10 @@ mod tests {
             assert_eq!(i as isize, rng.range_isize(i as isize..=i as isize));
             assert_eq!(i as isize, rng.range_isize(i as isize..i as isize + 1));
         }
+        for _ in 0..100 {
+            assert!((-5..5).contains(&rng.range_i64(-5..5)));
+            assert!((-5..=-3).contains(&rng.range_isize(-5..=-3)));
+        }
     }
 
     #[test]
@@ -0,0 +1,151 @@
+///強連結成分分解(Tarjan法)
+///
+///成分番号はトポロジカル順に振られる。(辺u->vがあればid(u)<=id(v))
+pub struct Scc {
+    count: usize,
+    ids: Vec<usize>,
+}
+
+impl Scc {
+    pub fn new(graph: &[Vec<usize>]) -> Scc {
+        let n = graph.len();
+        let mut ord = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut ids = vec![0; n];
+        let mut stack = vec![];
+        let mut call: Vec<(usize, usize)> = vec![];
+        let mut time = 0;
+        let mut count = 0;
+
+        for s in 0..n {
+            if ord[s] != usize::MAX {
+                continue;
+            }
+            ord[s] = time;
+            low[s] = time;
+            time += 1;
+            stack.push(s);
+            on_stack[s] = true;
+            call.push((s, 0));
+
+            while let Some(&mut (v, ref mut idx)) = call.last_mut() {
+                if *idx < graph[v].len() {
+                    let to = graph[v][*idx];
+                    *idx += 1;
+                    if ord[to] == usize::MAX {
+                        ord[to] = time;
+                        low[to] = time;
+                        time += 1;
+                        stack.push(to);
+                        on_stack[to] = true;
+                        call.push((to, 0));
+                    } else if on_stack[to] {
+                        low[v] = low[v].min(ord[to]);
+                    }
+                    continue;
+                }
+
+                call.pop();
+                if let Some(&(p, _)) = call.last() {
+                    low[p] = low[p].min(low[v]);
+                }
+                if low[v] == ord[v] {
+                    loop {
+                        let u = stack.pop().unwrap();
+                        on_stack[u] = false;
+                        ids[u] = count;
+                        if u == v {
+                            break;
+                        }
+                    }
+                    count += 1;
+                }
+            }
+        }
+
+        //Tarjan法では逆トポロジカル順に成分が確定する。
+        for id in ids.iter_mut() {
+            *id = count - 1 - *id;
+        }
+        Scc { count, ids }
+    }
+
+    ///強連結成分の個数
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    ///頂点vが属する成分番号
+    pub fn id(&self, v: usize) -> usize {
+        self.ids[v]
+    }
+
+    pub fn ids(&self) -> &[usize] {
+        &self.ids
+    }
+
+    ///成分ごとの頂点リストをトポロジカル順に返す。
+    pub fn groups(&self) -> Vec<Vec<usize>> {
+        let mut r = vec![vec![]; self.count];
+        for (v, &id) in self.ids.iter().enumerate() {
+            r[id].push(v);
+        }
+        r
+    }
+
+    ///縮約したDAGを隣接リストで返す。(多重辺・自己ループは除く)
+    pub fn condensation(&self, graph: &[Vec<usize>]) -> Vec<Vec<usize>> {
+        let mut r = vec![vec![]; self.count];
+        for (from, v) in graph.iter().enumerate() {
+            for &to in v {
+                if self.ids[from] != self.ids[to] {
+                    r[self.ids[from]].push(self.ids[to]);
+                }
+            }
+        }
+        for v in r.iter_mut() {
+            v.sort_unstable();
+            v.dedup();
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scc_works() {
+        let graph = vec![
+            vec![1],
+            vec![2, 3],
+            vec![0],
+            vec![4],
+            vec![5],
+            vec![3, 4],
+            vec![5],
+        ];
+        let scc = Scc::new(&graph);
+        assert_eq!(scc.count(), 3);
+        assert_eq!(scc.groups(), vec![vec![6], vec![0, 1, 2], vec![3, 4, 5]]);
+        for (from, v) in graph.iter().enumerate() {
+            for &to in v {
+                assert!(scc.id(from) <= scc.id(to));
+            }
+        }
+        assert_eq!(scc.condensation(&graph), vec![vec![2], vec![2], vec![]]);
+    }
+
+    #[test]
+    fn scc_long_path() {
+        let n = 1_000_000;
+        let mut graph = (0..n).map(|i| vec![(i + 1) % n]).collect::<Vec<_>>();
+        graph.push(vec![0]);
+        let scc = Scc::new(&graph);
+        assert_eq!(scc.count(), 2);
+        assert_eq!(scc.id(n), 0);
+        assert_eq!(scc.id(0), 1);
+    }
+}
@@ -0,0 +1,398 @@
+use crate::graph::Cost;
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+///頂点集合を表すビットマスク(u64, u128)
+pub trait BitMask:
+    Copy
+    + Eq
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+{
+    const BITS: usize;
+    fn empty() -> Self;
+    fn bit(i: usize) -> Self;
+    fn count(self) -> usize;
+    ///最も小さい要素(空集合に対して呼んではいけない)
+    fn lowest(self) -> usize;
+
+    fn is_empty(self) -> bool {
+        self == Self::empty()
+    }
+
+    fn contains(self, i: usize) -> bool {
+        !(self & Self::bit(i)).is_empty()
+    }
+
+    ///要素を小さい順に並べる。
+    fn to_vec(self) -> Vec<usize> {
+        let mut r = vec![];
+        let mut s = self;
+        while !s.is_empty() {
+            let v = s.lowest();
+            r.push(v);
+            s = s ^ Self::bit(v);
+        }
+        r
+    }
+}
+
+macro_rules! bitmask_impl {
+    ($($t:ty),*) => {
+        $(
+            impl BitMask for $t {
+                const BITS: usize = <$t>::BITS as usize;
+
+                fn empty() -> Self {
+                    0
+                }
+
+                fn bit(i: usize) -> Self {
+                    1 << i
+                }
+
+                fn count(self) -> usize {
+                    self.count_ones() as usize
+                }
+
+                fn lowest(self) -> usize {
+                    self.trailing_zeros() as usize
+                }
+            }
+        )*
+    };
+}
+
+bitmask_impl!(u64, u128);
+
+///隣接リストを各頂点の隣接頂点のビットマスクに変換する。(自己ループは無視する)
+pub fn adjacency_masks<B>(graph: &[Vec<usize>]) -> Vec<B>
+where
+    B: BitMask,
+{
+    assert!(graph.len() <= B::BITS);
+    graph
+        .iter()
+        .enumerate()
+        .map(|(v, adj)| {
+            adj.iter()
+                .filter(|&&to| to != v)
+                .fold(B::empty(), |m, &to| m | B::bit(to))
+        })
+        .collect()
+}
+
+///最大クリーク(ピボット選択つきBron-Kerbosch法)
+///
+///adj:無向グラフの隣接頂点のビットマスク
+pub fn max_clique<B>(adj: &[B]) -> Vec<usize>
+where
+    B: BitMask,
+{
+    let all = (0..adj.len()).fold(B::empty(), |m, v| m | B::bit(v));
+    let mut best = B::empty();
+    bron_kerbosch(adj, B::empty(), all, B::empty(), &mut best);
+    best.to_vec()
+}
+
+fn bron_kerbosch<B>(adj: &[B], r: B, mut p: B, mut x: B, best: &mut B)
+where
+    B: BitMask,
+{
+    if p.is_empty() {
+        if x.is_empty() && r.count() > best.count() {
+            *best = r;
+        }
+        return;
+    }
+    if r.count() + p.count() <= best.count() {
+        return;
+    }
+    //候補を最も多く含む頂点をピボットにする。
+    let pivot = (p | x)
+        .to_vec()
+        .into_iter()
+        .max_by_key(|&u| (p & adj[u]).count())
+        .unwrap();
+    let mut cand = p & !adj[pivot];
+    while !cand.is_empty() {
+        let v = cand.lowest();
+        cand = cand ^ B::bit(v);
+        bron_kerbosch(adj, r | B::bit(v), p & adj[v], x & adj[v], best);
+        p = p ^ B::bit(v);
+        x = x | B::bit(v);
+    }
+}
+
+///最大独立集合(分枝限定法)
+///
+///adj:無向グラフの隣接頂点のビットマスク
+pub fn max_independent_set<B>(adj: &[B]) -> Vec<usize>
+where
+    B: BitMask,
+{
+    let all = (0..adj.len()).fold(B::empty(), |m, v| m | B::bit(v));
+    let mut best = B::empty();
+    independent_set_rec(adj, all, B::empty(), &mut best);
+    best.to_vec()
+}
+
+fn independent_set_rec<B>(adj: &[B], mask: B, cur: B, best: &mut B)
+where
+    B: BitMask,
+{
+    if cur.count() + mask.count() <= best.count() {
+        return;
+    }
+    if mask.is_empty() {
+        *best = cur;
+        return;
+    }
+    let mut pick = None;
+    for v in mask.to_vec() {
+        let deg = (adj[v] & mask).count();
+        //次数1以下の頂点は選んでよい。
+        if deg <= 1 {
+            pick = Some((v, deg));
+            break;
+        }
+        if !matches!(pick, Some((_, d)) if d >= deg) {
+            pick = Some((v, deg));
+        }
+    }
+    let (v, deg) = pick.unwrap();
+    independent_set_rec(adj, mask & !(adj[v] | B::bit(v)), cur | B::bit(v), best);
+    if deg >= 2 {
+        independent_set_rec(adj, mask ^ B::bit(v), cur, best);
+    }
+}
+
+///彩色数(包除原理、O(2^n n))
+///
+///adj:無向グラフの隣接頂点のビットマスク
+///
+///独立集合の組の数を2つの素数で割った余りで数えるので、ごく低い確率で誤った値を返す。
+pub fn chromatic_number<B>(adj: &[B]) -> usize
+where
+    B: BitMask,
+{
+    const MODS: [u64; 2] = [998_244_353, 1_000_000_007];
+    let n = adj.len();
+    assert!(n < 32);
+    if n == 0 {
+        return 0;
+    }
+    let nb = adj
+        .iter()
+        .map(|&m| {
+            (0..n)
+                .filter(|&i| m.contains(i))
+                .fold(0_usize, |s, i| s | 1 << i)
+        })
+        .collect::<Vec<_>>();
+    //ind[s]:sに含まれる独立集合(空集合を含む)の個数
+    let mut ind = vec![0_u64; 1 << n];
+    ind[0] = 1;
+    for s in 1_usize..1 << n {
+        let v = s.trailing_zeros() as usize;
+        ind[s] = ind[s ^ 1 << v] + ind[s & !(nb[v] | 1 << v)];
+    }
+    let sign = (0_usize..1 << n)
+        .map(|s| (n - s.count_ones() as usize) % 2 == 1)
+        .collect::<Vec<_>>();
+    let mut pow = MODS
+        .iter()
+        .map(|&m| ind.iter().map(|&x| x % m).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    for k in 1..n {
+        //k個の独立集合で全体を覆う組の数が0でなければk色で塗れる。
+        for (j, &m) in MODS.iter().enumerate() {
+            let mut sum = 0;
+            for s in 0..1 << n {
+                if sign[s] {
+                    sum += m - pow[j][s];
+                } else {
+                    sum += pow[j][s];
+                }
+            }
+            if sum % m != 0 {
+                return k;
+            }
+        }
+        for (j, &m) in MODS.iter().enumerate() {
+            for s in 0..1 << n {
+                pow[j][s] = pow[j][s] * (ind[s] % m) % m;
+            }
+        }
+    }
+    n
+}
+
+///ハミルトン路(bitDP、O(2^n n^2))
+///
+///adj:隣接頂点のビットマスク(有向グラフでもよい)
+///
+///すべての頂点をちょうど1回ずつ通るパスを1つ返す。存在しなければNoneを返す。
+pub fn hamiltonian_path<B>(adj: &[B]) -> Option<Vec<usize>>
+where
+    B: BitMask,
+{
+    let n = adj.len();
+    assert!(n < 32);
+    if n == 0 {
+        return Some(vec![]);
+    }
+    let nb = adj
+        .iter()
+        .map(|&m| {
+            (0..n)
+                .filter(|&i| m.contains(i))
+                .fold(0_usize, |s, i| s | 1 << i)
+        })
+        .collect::<Vec<_>>();
+    //dp[s]:sをちょうど通るパスの終点になりうる頂点の集合
+    let mut dp = vec![0_usize; 1 << n];
+    for v in 0..n {
+        dp[1 << v] = 1 << v;
+    }
+    for s in 1..1 << n {
+        let mut ends = dp[s];
+        while ends != 0 {
+            let v = ends.trailing_zeros() as usize;
+            ends ^= 1 << v;
+            let mut next = nb[v] & !s;
+            while next != 0 {
+                let u = next.trailing_zeros() as usize;
+                next ^= 1 << u;
+                dp[s | 1 << u] |= 1 << u;
+            }
+        }
+    }
+
+    let mut s = (1 << n) - 1;
+    if dp[s] == 0 {
+        return None;
+    }
+    let mut v = dp[s].trailing_zeros() as usize;
+    let mut path = vec![v];
+    while s != 1 << v {
+        s ^= 1 << v;
+        v = (0..n)
+            .find(|&u| dp[s] >> u & 1 == 1 && nb[u] >> v & 1 == 1)
+            .unwrap();
+        path.push(v);
+    }
+    path.reverse();
+    Some(path)
+}
+
+///巡回セールスマン問題(bitDP、O(2^n n^2))
+///
+///dist:dist\[i\]\[j\]はiからjへ移動するコスト(移動できなければNone)
+///
+///cycleがtrueなら頂点0から出発して0に戻る巡回路、falseなら始点と終点が自由なパスの中でコスト最小のものを求める。
+///
+///(コスト, 頂点の訪問順)を返す。存在しなければNoneを返す。
+pub fn tsp<T>(dist: &[Vec<Option<T>>], cycle: bool) -> Option<(T, Vec<usize>)>
+where
+    T: Cost,
+{
+    let n = dist.len();
+    assert!(n < 32);
+    if n <= 1 {
+        return Some((T::zero(), (0..n).collect()));
+    }
+    let mut dp: Vec<Vec<Option<T>>> = vec![vec![None; n]; 1 << n];
+    if cycle {
+        dp[1][0] = Some(T::zero());
+    } else {
+        for v in 0..n {
+            dp[1 << v][v] = Some(T::zero());
+        }
+    }
+    for s in 1..1 << n {
+        for v in 0..n {
+            let d = match dp[s][v] {
+                Some(d) => d,
+                None => continue,
+            };
+            for u in 0..n {
+                if s >> u & 1 == 1 {
+                    continue;
+                }
+                if let Some(w) = dist[v][u] {
+                    let nd = d.plus(w);
+                    let t = s | 1 << u;
+                    if !matches!(dp[t][u], Some(x) if x <= nd) {
+                        dp[t][u] = Some(nd);
+                    }
+                }
+            }
+        }
+    }
+
+    let full = (1 << n) - 1;
+    //最後の頂点の候補と、そこまでのコスト(巡回路なら0に戻るコストも含める)
+    let (cost, mut v) = (0..n)
+        .filter_map(|v| {
+            let d = dp[full][v]?;
+            if cycle {
+                Some((d.plus(dist[v][0]?), v))
+            } else {
+                Some((d, v))
+            }
+        })
+        .min()?;
+    let mut s = full;
+    let mut order = vec![v];
+    while s != 1 << v {
+        let prev = s ^ 1 << v;
+        let d = dp[s][v].unwrap();
+        v = (0..n)
+            .find(|&u| matches!((dp[prev][u], dist[u][v]), (Some(x), Some(w)) if x.plus(w) == d))
+            .unwrap();
+        s = prev;
+        order.push(v);
+    }
+    order.reverse();
+    Some((cost, order))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_graph_works() {
+        //五角形に対角線を1本加えたグラフと孤立点
+        let graph = vec![
+            vec![1, 4, 2],
+            vec![0, 2],
+            vec![1, 3, 0],
+            vec![2, 4],
+            vec![3, 0],
+            vec![],
+        ];
+        let adj = adjacency_masks::<u64>(&graph);
+        assert_eq!(max_clique(&adj), vec![0, 1, 2]);
+        assert_eq!(max_independent_set(&adj).len(), 3);
+        assert_eq!(chromatic_number(&adj), 3);
+        assert_eq!(hamiltonian_path(&adj), None);
+        assert_eq!(hamiltonian_path(&adj[..5]), Some(vec![4, 3, 2, 1, 0]));
+
+        let adj = adjacency_masks::<u128>(&[vec![1, 3], vec![0, 2], vec![1, 3], vec![2, 0]]);
+        assert_eq!(max_clique(&adj).len(), 2);
+        assert_eq!(max_independent_set(&adj), vec![0, 2]);
+        assert_eq!(chromatic_number(&adj), 2);
+
+        let dist = vec![
+            vec![None, Some(1), Some(5), Some(2)],
+            vec![Some(1), None, Some(2), Some(4)],
+            vec![Some(5), Some(2), None, Some(1)],
+            vec![Some(2), Some(4), Some(1), None],
+        ];
+        assert_eq!(tsp(&dist, true), Some((6, vec![0, 3, 2, 1])));
+        assert_eq!(tsp(&dist, false), Some((4, vec![3, 2, 1, 0])));
+    }
+}
@@ -127,7 +127,7 @@ impl SmallRng for XorShift {
             std::ops::Bound::Unbounded => i64::MAX,
         };
 
-        start + self.next_i64() % (end - start)
+        start + self.next_i64().rem_euclid(end - start)
     }
 
     fn range_usize<R>(&mut self, r: R) -> usize
@@ -163,6 +163,6 @@ impl SmallRng for XorShift {
             std::ops::Bound::Unbounded => isize::MAX,
         };
 
-        start + self.next_isize() % (end - start)
+        start + self.next_isize().rem_euclid(end - start)
     }
 }